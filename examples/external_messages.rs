@@ -0,0 +1,38 @@
+//! Drive a running app from outside the VirtualDom
+//!
+//! Sometimes the data a window shows comes from somewhere else in the process - a launcher, a file watcher, a socket.
+//! This example spawns a thread that sends a counter over a channel, and a future inside the app that receives it and
+//! updates a signal. Futures in a desktop app are woken through the event loop, so every message reliably wakes the
+//! VirtualDom and triggers a re-render without any polling.
+
+use dioxus::prelude::*;
+use std::time::Duration;
+use tokio::sync::watch;
+
+fn main() {
+    let (tx, rx) = watch::channel(0);
+
+    // This thread stands in for any external source of messages
+    std::thread::spawn(move || loop {
+        std::thread::sleep(Duration::from_secs(1));
+        tx.send_modify(|count| *count += 1);
+    });
+
+    // Hand the receiving end to the app through the root context
+    dioxus::LaunchBuilder::desktop().with_context(rx).launch(app);
+}
+
+fn app() -> Element {
+    let mut count = use_signal(|| 0);
+
+    use_future(move || async move {
+        let mut rx = consume_context::<watch::Receiver<i32>>();
+        while rx.changed().await.is_ok() {
+            count.set(*rx.borrow_and_update());
+        }
+    });
+
+    rsx! {
+        h1 { "Messages received from another thread: {count}" }
+    }
+}
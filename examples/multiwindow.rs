@@ -17,7 +17,9 @@ fn main() {
 fn app() -> Element {
     let onclick = move |_| {
         let dom = VirtualDom::new(popup);
-        dioxus::desktop::window().new_window(dom, Default::default());
+        if let Err(err) = dioxus::desktop::window().new_window(dom, Default::default()) {
+            eprintln!("Couldn't open the window: {err}");
+        }
     };
 
     rsx! {
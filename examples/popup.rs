@@ -21,10 +21,11 @@ fn app() -> Element {
 
     let open_compose_window = move |_evt: MouseEvent| {
         let tx = handle.tx();
-        dioxus::desktop::window().new_window(
-            VirtualDom::new_with_props(popup, Rc::new(move |s| tx.unbounded_send(s).unwrap())),
-            Default::default(),
-        );
+        let dom =
+            VirtualDom::new_with_props(popup, Rc::new(move |s| tx.unbounded_send(s).unwrap()));
+        if let Err(err) = dioxus::desktop::window().new_window(dom, Default::default()) {
+            eprintln!("Couldn't open the compose window: {err}");
+        }
     };
 
     rsx! {
@@ -0,0 +1,44 @@
+//! Keep a heavy layout smooth while the window is resized
+//!
+//! Dragging the edge of a window resizes it many times a second. `use_window_resize_handler` only reports the size a
+//! few times a second and once more when the resizing stops, so the grid below isn't rebuilt on every frame.
+
+use dioxus::desktop::{use_window_resize_handler, LogicalSize};
+use dioxus::prelude::*;
+use std::time::Duration;
+
+fn main() {
+    dioxus::LaunchBuilder::desktop().launch(app);
+}
+
+fn app() -> Element {
+    let mut size = use_signal(|| LogicalSize::new(0.0, 0.0));
+    let mut renders = use_signal(|| 0);
+
+    use_window_resize_handler(Duration::from_millis(250), move |new_size| {
+        size.set(new_size);
+        renders += 1;
+    });
+
+    // One cell per 40 logical pixels, which gets expensive on a big window
+    let LogicalSize { width, height } = size();
+    let columns = (width / 40.0).max(1.0) as usize;
+    let rows = (height / 40.0).max(1.0) as usize;
+    let hues = (0..rows * columns).map(|cell| cell * 13 % 360);
+
+    rsx! {
+        h1 { "{width:.0} x {height:.0}" }
+        p { "Rendered {renders} times for resizes" }
+        div {
+            display: "grid",
+            grid_template_columns: "repeat({columns}, 1fr)",
+            for (cell, hue) in hues.enumerate() {
+                div {
+                    key: "{cell}",
+                    height: "36px",
+                    background_color: "hsl({hue}, 60%, 70%)",
+                }
+            }
+        }
+    }
+}
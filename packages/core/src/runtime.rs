@@ -170,14 +170,8 @@ impl Runtime {
     /// Useful in a limited number of scenarios
     pub fn on_scope<O>(self: &Rc<Self>, id: ScopeId, f: impl FnOnce() -> O) -> O {
         let _runtime_guard = RuntimeGuard::new(self.clone());
-        {
-            self.push_scope(id);
-        }
-        let o = f();
-        {
-            self.pop_scope();
-        }
-        o
+        let _scope_guard = self.push_scope(id);
+        f()
     }
 
     /// Get the current suspense location
@@ -192,21 +186,18 @@ impl Runtime {
         f: impl FnOnce() -> O,
     ) -> O {
         self.suspense_stack.borrow_mut().push(suspense_location);
-        let o = f();
-        self.suspense_stack.borrow_mut().pop();
-        o
+        let _suspense_guard = SuspenseStackGuard(self);
+        f()
     }
 
     /// Run a callback with the current scope at the top of the stack
     pub(crate) fn with_scope_on_stack<O>(&self, scope: ScopeId, f: impl FnOnce() -> O) -> O {
-        self.push_scope(scope);
-        let o = f();
-        self.pop_scope();
-        o
+        let _scope_guard = self.push_scope(scope);
+        f()
     }
 
-    /// Push a scope onto the stack
-    fn push_scope(&self, scope: ScopeId) {
+    /// Push a scope onto the stack. It is popped when the guard is dropped, even if the caller panics.
+    fn push_scope(&self, scope: ScopeId) -> ScopeStackGuard<'_> {
         let suspense_location = self
             .scope_states
             .borrow()
@@ -216,6 +207,7 @@ impl Runtime {
             .unwrap_or_default();
         self.suspense_stack.borrow_mut().push(suspense_location);
         self.scope_stack.borrow_mut().push(scope);
+        ScopeStackGuard(self)
     }
 
     /// Pop a scope off the stack
@@ -484,6 +476,26 @@ impl Drop for RuntimeGuard {
     }
 }
 
+/// Pops the scope pushed by [`Runtime::push_scope`] when dropped. A panic caught further up, like in an event handler
+/// of a renderer, would otherwise leave the scope on the stack and every later lookup would find the wrong scope.
+struct ScopeStackGuard<'a>(&'a Runtime);
+
+impl Drop for ScopeStackGuard<'_> {
+    fn drop(&mut self) {
+        self.0.pop_scope();
+    }
+}
+
+/// Pops the suspense location pushed by [`Runtime::with_suspense_location`] when dropped, for the same reason as
+/// [`ScopeStackGuard`]
+struct SuspenseStackGuard<'a>(&'a Runtime);
+
+impl Drop for SuspenseStackGuard<'_> {
+    fn drop(&mut self) {
+        self.0.suspense_stack.borrow_mut().pop();
+    }
+}
+
 /// Missing Dioxus runtime error.
 pub struct RuntimeError {
     _priv: (),
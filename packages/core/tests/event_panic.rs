@@ -0,0 +1,70 @@
+//! Verify that a panic caught while handling an event doesn't leave its scope on the runtime's stack
+
+use dioxus::prelude::*;
+use dioxus_core::{prelude::current_scope_id, ElementId};
+use std::{
+    any::Any,
+    panic::AssertUnwindSafe,
+    rc::Rc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+    time::Duration,
+};
+
+static PANICKED: AtomicBool = AtomicBool::new(false);
+static HANDLED_IN: Mutex<Option<ScopeId>> = Mutex::new(None);
+static SPAWNED: AtomicBool = AtomicBool::new(false);
+
+#[tokio::test]
+async fn caught_panic_pops_the_scope() {
+    set_event_converter(Box::new(dioxus::html::SerializedHtmlEventConverter));
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild(&mut dioxus_core::NoOpMutations);
+
+    let runtime = dom.runtime();
+    let click = || {
+        Event::new(
+            Rc::new(PlatformEventData::new(Box::<SerializedMouseData>::default())) as Rc<dyn Any>,
+            true,
+        )
+    };
+
+    // Renderers catch panics in handlers to keep the app running
+    let result = std::panic::catch_unwind(AssertUnwindSafe(|| {
+        runtime.handle_event("click", click(), ElementId(1))
+    }));
+    assert!(result.is_err());
+
+    // The scope of the handler that panicked isn't current anymore
+    dom.in_runtime(|| assert!(current_scope_id().is_err()));
+
+    // And the next event runs in the right scope and can spawn tasks
+    runtime.handle_event("click", click(), ElementId(1));
+    assert_eq!(*HANDLED_IN.lock().unwrap(), Some(ScopeId::APP));
+
+    tokio::select! {
+        _ = dom.wait_for_work() => {}
+        _ = tokio::time::sleep(Duration::from_millis(100)) => {}
+    };
+    assert!(SPAWNED.load(Ordering::SeqCst));
+}
+
+fn app() -> Element {
+    rsx! {
+        button {
+            onclick: move |_| {
+                if !PANICKED.swap(true, Ordering::SeqCst) {
+                    panic!("the first click panics");
+                }
+
+                *HANDLED_IN.lock().unwrap() = current_scope_id().ok();
+                spawn(async {
+                    SPAWNED.store(true, Ordering::SeqCst);
+                });
+            },
+        }
+    }
+}
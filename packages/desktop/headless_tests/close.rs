@@ -0,0 +1,56 @@
+use dioxus::prelude::*;
+use dioxus_desktop::window;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+#[path = "./utils.rs"]
+mod utils;
+
+/// How often the close handler ran. If the canceled close removed the window, the second close would never reach it.
+static CLOSE_REQUESTS: AtomicUsize = AtomicUsize::new(0);
+
+pub fn main() {
+    #[cfg(not(windows))]
+    utils::check_app_exits(app);
+}
+
+fn app() -> Element {
+    // The exit handler runs in this component, which is still around since the window closing is the last one
+    let mut confirmed = use_signal(|| false);
+
+    use_hook(|| {
+        window().set_close_handler(move || {
+            // Cancel the first close and let the second one through
+            let confirm = CLOSE_REQUESTS.fetch_add(1, Ordering::SeqCst) > 0;
+            confirmed.set(confirm);
+            async move { confirm }
+        });
+
+        // Launching never returns, so check the count on the way out
+        window().register_exit_handler(move || {
+            let requests = CLOSE_REQUESTS.load(Ordering::SeqCst);
+            if requests != 2 {
+                eprintln!("The close handler ran {requests} times instead of 2");
+                std::process::exit(exitcode::SOFTWARE);
+            }
+
+            if !confirmed.try_read().is_ok_and(|confirmed| *confirmed) {
+                eprintln!("The exit handler couldn't read the state of the window that closed");
+                std::process::exit(exitcode::SOFTWARE);
+            }
+        });
+    });
+
+    use_future(|| async {
+        window().close();
+        while CLOSE_REQUESTS.load(Ordering::SeqCst) == 0 {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+
+        // Give the canceled close time to be handled before closing for real
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        window().close();
+    });
+
+    rsx! {}
+}
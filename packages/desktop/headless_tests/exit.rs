@@ -0,0 +1,39 @@
+use dioxus::prelude::*;
+use dioxus_desktop::{use_app_state_handler, window, AppState};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+#[path = "./utils.rs"]
+mod utils;
+
+/// If the app state handler saw the app exit. The window it was registered from is the last one, so it has to hear
+/// about the exit before the window is dropped.
+static SAW_EXIT: AtomicBool = AtomicBool::new(false);
+
+pub fn main() {
+    #[cfg(not(windows))]
+    utils::check_app_exits(app);
+}
+
+fn app() -> Element {
+    use_app_state_handler(|state| {
+        if state == AppState::Exiting {
+            SAW_EXIT.store(true, Ordering::SeqCst);
+        }
+    });
+
+    use_hook(|| {
+        // Launching never returns, so check on the way out
+        window().register_exit_handler(|| {
+            if !SAW_EXIT.load(Ordering::SeqCst) {
+                eprintln!("The app state handler didn't run when the last window closed");
+                std::process::exit(exitcode::SOFTWARE);
+            }
+        });
+    });
+
+    use_future(|| async {
+        window().close();
+    });
+
+    rsx! {}
+}
@@ -0,0 +1,108 @@
+use std::fmt::{self, Display};
+
+/// The name of an action bound to a keyboard shortcut with
+/// [`Config::with_accelerators`](crate::Config::with_accelerators).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ActionId(String);
+
+impl ActionId {
+    /// Create an action id from a name, like `"save"`
+    pub fn new(name: impl Into<String>) -> Self {
+        Self(name.into())
+    }
+
+    /// Get the name of the action
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&str> for ActionId {
+    fn from(name: &str) -> Self {
+        Self::new(name)
+    }
+}
+
+impl From<String> for ActionId {
+    fn from(name: String) -> Self {
+        Self::new(name)
+    }
+}
+
+impl Display for ActionId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Menu ids are global, so ours are prefixed to keep them apart from the items of the menu bar
+#[cfg(not(any(target_os = "ios", target_os = "android")))]
+const MENU_ID_PREFIX: &str = "dioxus-action:";
+
+/// Build the menu bar of a window with the accelerators of its config installed.
+///
+/// The OS only routes key combinations to menus, so the accelerators are items of a "Shortcuts" submenu. Items of the
+/// default menu bar that would use the same accelerators are left out, so the table always wins over them.
+#[cfg(not(any(target_os = "ios", target_os = "android")))]
+pub(crate) fn build_menu_bar(
+    menu: crate::config::MenuBuilderState,
+    table: &[(muda::accelerator::Accelerator, ActionId)],
+) -> Option<muda::Menu> {
+    use crate::config::MenuBuilderState;
+    use muda::{MenuItem, Submenu};
+
+    let mut table = table.to_vec();
+    let mut seen = Vec::with_capacity(table.len());
+    table.retain(|(accelerator, action)| {
+        if seen.contains(accelerator) {
+            tracing::warn!(
+                "The accelerator {accelerator:?} of {action} is already bound to an earlier action, ignoring it"
+            );
+            return false;
+        }
+        seen.push(*accelerator);
+        true
+    });
+
+    let menu = match menu {
+        MenuBuilderState::Unset => Some(crate::menubar::default_menu_bar_without(&seen)),
+        MenuBuilderState::Set(menu) => menu,
+    };
+
+    if table.is_empty() {
+        return menu;
+    }
+
+    let Some(menu) = menu else {
+        tracing::warn!(
+            "The window has no menu bar, so its accelerators can't be installed. Windows without decorations don't get \
+            the default menu bar."
+        );
+        return None;
+    };
+
+    let shortcuts = Submenu::new("Shortcuts", true);
+    for (accelerator, action) in table {
+        let item = MenuItem::with_id(
+            format!("{MENU_ID_PREFIX}{action}"),
+            action.as_str(),
+            true,
+            Some(accelerator),
+        );
+        if let Err(err) = shortcuts.append(&item) {
+            tracing::error!("Failed to install the accelerator of {action}: {err}");
+        }
+    }
+
+    if let Err(err) = menu.append(&shortcuts) {
+        tracing::error!("Failed to install the accelerators: {err}");
+    }
+
+    Some(menu)
+}
+
+/// Get the action of the accelerator that triggered this menu event, if it came from one
+#[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
+pub(crate) fn action_id(event: &muda::MenuEvent) -> Option<ActionId> {
+    event.id().0.strip_prefix(MENU_ID_PREFIX).map(ActionId::new)
+}
@@ -1,13 +1,19 @@
 use crate::{
+    commands::{CommandResponder, InvokeRequest},
     config::{Config, WindowCloseBehaviour},
+    context_menu::ContextMenuRequest,
     event_handlers::WindowEventHandlers,
+    events::handle_event_catching_panics,
     file_upload::{DesktopFileUploadForm, FileDialogRequest, NativeFileEngine},
+    hooks::{AppState, WindowFocusEvent, WindowLifecycleEvent},
     ipc::{IpcMessage, UserWindowEvent},
+    network::OnlineStatus,
     query::QueryResult,
     shortcut::ShortcutRegistry,
-    webview::WebviewInstance,
+    webview::{Navigation, WebviewInstance},
+    DesktopService, MediaPreferences,
 };
-use dioxus_core::{ElementId, VirtualDom};
+use dioxus_core::{ElementId, VNode, VirtualDom};
 use dioxus_html::PlatformEventData;
 use std::{
     any::Any,
@@ -37,6 +43,20 @@ pub(crate) struct App {
     pub(crate) webviews: HashMap<WindowId, WebviewInstance>,
     pub(crate) float_all: bool,
     pub(crate) show_devtools: bool,
+    #[cfg(feature = "tokio_runtime")]
+    pub(crate) exit_grace_period: std::time::Duration,
+    pub(crate) remember_window_state: bool,
+
+    /// The window of this app that has focus, if any
+    pub(crate) focused_window: Option<WindowId>,
+
+    /// The window and element a custom context menu was last opened for
+    #[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
+    pub(crate) context_menu_target: Option<(WindowId, crate::ContextMenuTarget)>,
+
+    /// The system clipboard, opened lazily the first time a component touches it
+    #[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
+    pub(crate) clipboard: Option<arboard::Clipboard>,
 
     /// This single blob of state is shared between all the windows so they have access to the runtime state
     ///
@@ -48,7 +68,10 @@ pub(crate) struct App {
 pub(crate) struct SharedContext {
     pub(crate) event_handlers: WindowEventHandlers,
     pub(crate) pending_webviews: RefCell<Vec<WebviewInstance>>,
+    pub(crate) pending_roots: RefCell<Vec<(WindowId, VirtualDom)>>,
     pub(crate) shortcut_manager: ShortcutRegistry,
+    pub(crate) exit_handlers: RefCell<Vec<Box<dyn FnOnce()>>>,
+    pub(crate) app_state: Cell<AppState>,
     pub(crate) proxy: EventLoopProxy<UserWindowEvent>,
     pub(crate) target: EventLoopWindowTarget<UserWindowEvent>,
 }
@@ -60,6 +83,12 @@ impl App {
             .take()
             .unwrap_or_else(|| EventLoopBuilder::<UserWindowEvent>::with_user_event().build());
 
+        // The tasks on a runtime the app was handed belong to the rest of the program, so they aren't waited for
+        #[cfg(feature = "tokio_runtime")]
+        if matches!(cfg.runtime_flavor, crate::RuntimeFlavor::External(_)) {
+            cfg.exit_grace_period = std::time::Duration::ZERO;
+        }
+
         let app = Self {
             window_behavior: cfg.last_window_close_behavior,
             is_visible_before_start: true,
@@ -68,14 +97,25 @@ impl App {
             unmounted_dom: Cell::new(Some(virtual_dom)),
             float_all: false,
             show_devtools: false,
-            cfg: Cell::new(Some(cfg)),
+            #[cfg(feature = "tokio_runtime")]
+            exit_grace_period: cfg.exit_grace_period,
+            remember_window_state: cfg.remember_window_state,
+            focused_window: None,
+            #[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
+            context_menu_target: None,
+            #[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
+            clipboard: None,
             shared: Rc::new(SharedContext {
                 event_handlers: WindowEventHandlers::default(),
                 pending_webviews: Default::default(),
+                pending_roots: Default::default(),
                 shortcut_manager: ShortcutRegistry::new(),
+                exit_handlers: RefCell::new(cfg.exit_handler.take().into_iter().collect()),
+                app_state: Cell::new(AppState::Running),
                 proxy: event_loop.create_proxy(),
                 target: event_loop.clone(),
             }),
+            cfg: Cell::new(Some(cfg)),
         };
 
         // Set the event converter
@@ -116,8 +156,80 @@ impl App {
         self.shared.shortcut_manager.call_handlers(event);
     }
 
+    #[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
+    pub fn handle_clipboard_request(&mut self, request: crate::ipc::ClipboardRequest) {
+        use crate::ipc::ClipboardRequest;
+
+        if self.clipboard.is_none() {
+            match arboard::Clipboard::new() {
+                Ok(clipboard) => self.clipboard = Some(clipboard),
+                Err(err) => tracing::error!("Failed to open the system clipboard: {err}"),
+            }
+        }
+
+        match request {
+            ClipboardRequest::SetText(text) => {
+                let Some(clipboard) = self.clipboard.as_mut() else {
+                    return;
+                };
+                if let Err(err) = clipboard.set_text(text) {
+                    tracing::error!("Failed to write to the clipboard: {err}");
+                }
+            }
+            ClipboardRequest::GetText(sender) => {
+                // Non-text contents (images, files, etc) show up as an error - we just treat them as no text
+                let text = self
+                    .clipboard
+                    .as_mut()
+                    .and_then(|clipboard| clipboard.get_text().ok());
+                _ = sender.unbounded_send(text);
+            }
+            ClipboardRequest::SetImage(image) => {
+                let Some(clipboard) = self.clipboard.as_mut() else {
+                    return;
+                };
+                if let Err(err) = clipboard.set_image(image) {
+                    tracing::error!("Failed to write the image to the clipboard: {err}");
+                }
+            }
+            ClipboardRequest::GetImage(sender) => {
+                // Like text, contents that aren't an image show up as an error
+                let image = self
+                    .clipboard
+                    .as_mut()
+                    .and_then(|clipboard| clipboard.get_image().ok())
+                    .map(crate::clipboard::ImageData::from_arboard);
+                _ = sender.unbounded_send(image);
+            }
+        }
+    }
+
     #[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
     pub fn handle_menu_event(&mut self, event: muda::MenuEvent) {
+        if let Some(action) = crate::accelerators::action_id(&event) {
+            // On macOS the menu bar is shared by every window, so the focused window gets the action
+            if let Some(id) = self.focused_window {
+                _ = self
+                    .shared
+                    .proxy
+                    .send_event(UserWindowEvent::Accelerator { id, action });
+            }
+            return;
+        }
+
+        if let Some(item_id) = crate::context_menu::item_id(&event) {
+            if let Some((id, target)) = self.context_menu_target.take() {
+                _ = self.shared.proxy.send_event(UserWindowEvent::ContextMenu {
+                    id,
+                    event: crate::ContextMenuEvent {
+                        item_id: item_id.to_string(),
+                        target,
+                    },
+                });
+            }
+            return;
+        }
+
         match event.id().0.as_str() {
             "dioxus-float-top" => {
                 for webview in self.webviews.values() {
@@ -131,11 +243,10 @@ impl App {
             "dioxus-toggle-dev-tools" => {
                 self.show_devtools = !self.show_devtools;
                 for webview in self.webviews.values() {
-                    let wv = &webview.desktop_context.webview;
                     if self.show_devtools {
-                        wv.open_devtools();
+                        webview.desktop_context.open_devtools();
                     } else {
-                        wv.close_devtools();
+                        webview.desktop_context.close_devtools();
                     }
                 }
             }
@@ -177,29 +288,102 @@ impl App {
     }
 
     pub fn handle_new_window(&mut self) {
-        for handler in self.shared.pending_webviews.borrow_mut().drain(..) {
+        let pending = self.shared.pending_webviews.take();
+        for handler in pending {
             let id = handler.desktop_context.window.id();
-            self.webviews.insert(id, handler);
+            self.add_webview(handler);
             _ = self.shared.proxy.send_event(UserWindowEvent::Poll(id));
         }
     }
 
+    /// Open a url in a new window that follows the policies of the window that asked for it. It's added to the other
+    /// windows right away, so it's closed and cleaned up like them.
+    pub fn handle_open_in_new_window(&mut self, opener: WindowId, url: String) {
+        let Some(view) = self.webviews.get(&opener) else {
+            return;
+        };
+
+        let cfg = Config::for_opened_window(view.opened_window_settings.clone());
+
+        // The page replaces the app's own page once it loads, so there is nothing to render
+        let view = WebviewInstance::new(cfg, VirtualDom::new(VNode::empty), self.shared.clone());
+        let id = view.desktop_context.window.id();
+        self.add_webview(view);
+        self.handle_navigate(id, Navigation::Url(url));
+        _ = self.shared.proxy.send_event(UserWindowEvent::Poll(id));
+    }
+
+    /// The user or the app asked to close the window. A close handler can still cancel it, so the window is kept until
+    /// the close is confirmed.
     pub fn handle_close_requested(&mut self, id: WindowId) {
+        // If a component wants to confirm the close, let it decide. It'll send a CloseConfirmed event if we can proceed
+        if let Some(webview) = self.webviews.get(&id) {
+            let close_handler = &webview.desktop_context.close_handler;
+
+            // Take the handler out while it runs so it can replace or remove itself. It only goes back if nothing set
+            // or removed a handler in the meantime, and the scope that set it is still around to ask.
+            let generation = webview.desktop_context.close_handler_generation.get();
+            let handler = close_handler.borrow_mut().take();
+            if let Some(mut handler) = handler {
+                if handler() {
+                    if webview.desktop_context.close_handler_generation.get() == generation {
+                        *close_handler.borrow_mut() = Some(handler);
+                    }
+                    return;
+                }
+            }
+        }
+
+        self.handle_close_confirmed(id)
+    }
+
+    /// The close went through, so the window is dropped, which destroys it. Clicking close again while a close handler
+    /// is still deciding can confirm the same close twice, and only the first one counts.
+    pub fn handle_close_confirmed(&mut self, id: WindowId) {
         use WindowCloseBehaviour::*;
 
+        if !self.webviews.contains_key(&id) {
+            return;
+        }
+
+        // Windows can override the app-wide close behaviour when they're created
+        match self
+            .webviews
+            .get(&id)
+            .and_then(|view| view.exit_app_on_close)
+        {
+            Some(true) => {
+                self.persist_window_state();
+
+                // Components are told before the window and its components are dropped
+                self.handle_exiting();
+                self.remove_webview(id);
+                self.control_flow = ControlFlow::Exit;
+                return;
+            }
+            Some(false) => {
+                self.remove_webview(id);
+                return;
+            }
+            None => {}
+        }
+
         match self.window_behavior {
             LastWindowExitsApp => {
-                #[cfg(debug_assertions)]
                 self.persist_window_state();
 
-                self.webviews.remove(&id);
-                if self.webviews.is_empty() {
+                let last_window = self.webviews.len() == 1;
+                if last_window {
+                    self.handle_exiting();
+                }
+                self.remove_webview(id);
+                if last_window {
                     self.control_flow = ControlFlow::Exit
                 }
             }
 
             LastWindowHides if self.webviews.len() > 1 => {
-                self.webviews.remove(&id);
+                self.remove_webview(id);
             }
 
             LastWindowHides => {
@@ -209,19 +393,51 @@ impl App {
             }
 
             CloseWindow => {
-                self.webviews.remove(&id);
+                self.remove_webview(id);
             }
         }
     }
 
-    pub fn window_destroyed(&mut self, id: WindowId) {
-        self.webviews.remove(&id);
+    /// Start tracking a window and tell components it exists
+    fn add_webview(&mut self, webview: WebviewInstance) {
+        let id = webview.desktop_context.window.id();
+        tracing::debug!("Opened window {id:?}");
+        self.webviews.insert(id, webview);
+        _ = self
+            .shared
+            .proxy
+            .send_event(UserWindowEvent::WindowLifecycle {
+                id,
+                event: WindowLifecycleEvent::Created,
+            });
+    }
+
+    /// Drop a window and tell components it's gone. The window may have been removed already when it's destroyed.
+    fn remove_webview(&mut self, id: WindowId) {
+        if self.webviews.remove(&id).is_some() {
+            tracing::debug!("Closed window {id:?}, {} left", self.webviews.len());
+            _ = self
+                .shared
+                .proxy
+                .send_event(UserWindowEvent::WindowLifecycle {
+                    id,
+                    event: WindowLifecycleEvent::Destroyed,
+                });
+        }
+    }
 
-        if matches!(
+    /// The window is gone. It usually was dropped when its close was confirmed, but the OS can also destroy it directly.
+    pub fn window_destroyed(&mut self, id: WindowId) {
+        let exits = matches!(
             self.window_behavior,
             WindowCloseBehaviour::LastWindowExitsApp
-        ) && self.webviews.is_empty()
-        {
+        ) && self.webviews.keys().all(|window| *window == id);
+
+        if exits {
+            self.handle_exiting();
+        }
+        self.remove_webview(id);
+        if exits {
             self.control_flow = ControlFlow::Exit
         }
     }
@@ -243,6 +459,33 @@ impl App {
         }
     }
 
+    pub fn handle_focus_changed(&mut self, id: WindowId, focused: bool) {
+        if focused {
+            self.focused_window = Some(id);
+            _ = self.shared.proxy.send_event(UserWindowEvent::FocusChanged {
+                id,
+                event: WindowFocusEvent::Gained,
+            });
+            return;
+        }
+
+        // The window that takes focus next is told after this one loses it, so look at what happened once the event
+        // loop caught up with the rest of the focus change.
+        _ = self.shared.proxy.send_event(UserWindowEvent::FocusLost(id));
+    }
+
+    pub fn handle_focus_lost(&mut self, id: WindowId) {
+        let to_app_window = self.focused_window.is_some_and(|focused| focused != id);
+        if self.focused_window == Some(id) {
+            self.focused_window = None;
+        }
+
+        _ = self.shared.proxy.send_event(UserWindowEvent::FocusChanged {
+            id,
+            event: WindowFocusEvent::Lost { to_app_window },
+        });
+    }
+
     pub fn handle_start_cause_init(&mut self) {
         let virtual_dom = self
             .unmounted_dom
@@ -263,27 +506,94 @@ impl App {
         // And then attempt to resume from state
         self.resume_from_state(&webview, explicit_window_size, explicit_window_position);
 
-        let id = webview.desktop_context.window.id();
-        self.webviews.insert(id, webview);
+        self.add_webview(webview);
     }
 
-    pub fn handle_browser_open(&mut self, msg: IpcMessage) {
+    pub fn handle_browser_open(&mut self, msg: IpcMessage, id: WindowId) {
+        let Some(view) = self.webviews.get(&id) else {
+            return;
+        };
+
         if let Some(temp) = msg.params().as_object() {
-            if temp.contains_key("href") {
-                if let Some(href) = temp.get("href").and_then(|v| v.as_str()) {
-                    if let Err(e) = webbrowser::open(href) {
-                        tracing::error!("Open Browser error: {:?}", e);
-                    }
-                }
+            if let Some(href) = temp.get("href").and_then(|v| v.as_str()) {
+                view.external_link_policy.open(href);
+            }
+        }
+    }
+
+    /// A drag region of the page was pressed
+    pub fn handle_drag_window_msg(&mut self, id: WindowId) {
+        if let Some(view) = self.webviews.get(&id) {
+            view.desktop_context.drag();
+        }
+    }
+
+    /// A drag region of the page was double clicked
+    pub fn handle_toggle_maximize_msg(&mut self, id: WindowId) {
+        if let Some(view) = self.webviews.get(&id) {
+            view.desktop_context.toggle_maximized();
+        }
+    }
+
+    /// Deserialize the params of a message from the webview of a window, counting the message as dropped if they
+    /// don't fit
+    fn parse_params<T: serde::de::DeserializeOwned>(
+        &self,
+        msg: IpcMessage,
+        id: WindowId,
+    ) -> Option<T> {
+        let view = self.webviews.get(&id)?;
+        msg.parse_params(&view.desktop_context.invalid_messages)
+    }
+
+    /// The page was right-clicked in a window with a custom context menu
+    pub fn handle_context_menu_msg(&mut self, msg: IpcMessage, id: WindowId) {
+        let Some(request) = self.parse_params::<ContextMenuRequest>(msg, id) else {
+            return;
+        };
+
+        // Other platforms don't have native context menus, the page only cancels the webview's own menu there
+        #[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
+        if let Some(view) = self.webviews.get(&id) {
+            if let Some(menu) = &view.context_menu {
+                self.context_menu_target = Some((id, request.target));
+                crate::context_menu::show_menu(
+                    menu,
+                    &view.desktop_context.window,
+                    request.x,
+                    request.y,
+                );
             }
         }
+
+        #[cfg(not(any(target_os = "windows", target_os = "linux", target_os = "macos")))]
+        {
+            _ = (request, id);
+        }
     }
 
     /// The webview is finally loaded
     ///
     /// Let's rebuild it and then start polling it
     pub fn handle_initialize_msg(&mut self, id: WindowId) {
-        let view = self.webviews.get_mut(&id).unwrap();
+        let Some(view) = self.webviews.get_mut(&id) else {
+            tracing::warn!(
+                "Received an initialize message for a window that no longer exists: {id:?}"
+            );
+            return;
+        };
+
+        view.initialized = true;
+        view.desktop_context.apply_spellcheck();
+
+        // The page is about to be replaced, so let the page that replaces it ask for the rebuild instead
+        if let Some(navigation) = view.take_pending_navigation() {
+            view.navigate(navigation);
+            view.desktop_context
+                .window
+                .set_visible(self.is_visible_before_start);
+            return;
+        }
 
         view.edits
             .wry_queue
@@ -298,18 +608,43 @@ impl App {
         _ = self.shared.proxy.send_event(UserWindowEvent::Poll(id));
     }
 
-    /// Todo: maybe we should poll the virtualdom asking if it has any final actions to apply before closing the webview
-    ///
-    /// Technically you can handle this with the use_window_event hook
-    pub fn handle_close_msg(&mut self, id: WindowId) {
-        self.webviews.remove(&id);
-        if self.webviews.is_empty() {
-            self.control_flow = ControlFlow::Exit
+    pub fn handle_replace_root(&mut self, id: WindowId) {
+        let pending = std::mem::take(&mut *self.shared.pending_roots.borrow_mut());
+        for (window_id, dom) in pending {
+            if window_id != id {
+                self.shared
+                    .pending_roots
+                    .borrow_mut()
+                    .push((window_id, dom));
+                continue;
+            }
+
+            if let Some(view) = self.webviews.get_mut(&id) {
+                view.replace_root(dom);
+            }
+        }
+    }
+
+    pub fn handle_navigate(&mut self, id: WindowId, navigation: Navigation) {
+        if let Some(view) = self.webviews.get_mut(&id) {
+            view.navigate(navigation);
+        }
+    }
+
+    pub fn handle_set_progress(&mut self, id: WindowId, progress: Option<f64>) {
+        if let Some(view) = self.webviews.get(&id) {
+            crate::taskbar::set_progress(&view.desktop_context.window, progress);
+        }
+    }
+
+    pub fn handle_set_badge(&mut self, id: WindowId, badge: Option<String>) {
+        if let Some(view) = self.webviews.get(&id) {
+            crate::taskbar::set_badge(&view.desktop_context.window, badge);
         }
     }
 
     pub fn handle_query_msg(&mut self, msg: IpcMessage, id: WindowId) {
-        let Ok(result) = serde_json::from_value::<QueryResult>(msg.params()) else {
+        let Some(result) = self.parse_params::<QueryResult>(msg, id) else {
             return;
         };
 
@@ -320,6 +655,54 @@ impl App {
         view.desktop_context.query.send(result);
     }
 
+    pub fn handle_invoke_msg(&mut self, msg: IpcMessage, id: WindowId) {
+        let Some(request) = self.parse_params::<InvokeRequest>(msg, id) else {
+            return;
+        };
+
+        let Some(view) = self.webviews.get(&id) else {
+            return;
+        };
+
+        let responder = CommandResponder::new(request.id, Rc::downgrade(&view.desktop_context));
+        view.desktop_context.commands.invoke(request, responder);
+    }
+
+    pub fn handle_media_preferences_msg(&mut self, msg: IpcMessage, id: WindowId) {
+        let Some(preferences) = self.parse_params::<MediaPreferences>(msg, id) else {
+            return;
+        };
+
+        let Some(view) = self.webviews.get(&id) else {
+            return;
+        };
+
+        // Every matching query reports the change, so only pass on the first report of it
+        if view.desktop_context.media_preferences.replace(preferences) != preferences {
+            _ = self
+                .shared
+                .proxy
+                .send_event(UserWindowEvent::MediaPreferencesChanged { id, preferences });
+        }
+    }
+
+    pub fn handle_online_status_msg(&mut self, msg: IpcMessage, id: WindowId) {
+        let Some(OnlineStatus { online }) = self.parse_params(msg, id) else {
+            return;
+        };
+
+        let Some(view) = self.webviews.get(&id) else {
+            return;
+        };
+
+        if view.desktop_context.online.replace(online) != online {
+            _ = self
+                .shared
+                .proxy
+                .send_event(UserWindowEvent::OnlineStatusChanged { id, online });
+        }
+    }
+
     #[cfg(all(feature = "devtools", debug_assertions))]
     pub fn handle_hot_reload_msg(&mut self, msg: dioxus_devtools::DevserverMsg) {
         use dioxus_devtools::DevserverMsg;
@@ -350,7 +733,7 @@ impl App {
     }
 
     pub fn handle_file_dialog_msg(&mut self, msg: IpcMessage, window: WindowId) {
-        let Ok(file_dialog) = serde_json::from_value::<FileDialogRequest>(msg.params()) else {
+        let Some(file_dialog) = self.parse_params::<FileDialogRequest>(msg, window) else {
             return;
         };
 
@@ -373,10 +756,10 @@ impl App {
 
         let runtime = view.dom.runtime();
         if event_name == "change&input" {
-            runtime.handle_event("input", event.clone(), id);
-            runtime.handle_event("change", event, id);
+            handle_event_catching_panics(&runtime, "input", event.clone(), id);
+            handle_event_catching_panics(&runtime, "change", event, id);
         } else {
-            runtime.handle_event(event_name, event, id);
+            handle_event_catching_panics(&runtime, event_name, event, id);
         }
     }
 
@@ -442,98 +825,98 @@ impl App {
         }));
     }
 
+    /// Tell components the app is exiting and run the exit handlers, once. The event loop won't deliver another event,
+    /// so the wry event handlers are called right away, while the windows that are still open exist. Closing the last
+    /// window calls this before the window is dropped, since its components would otherwise be gone by the time the
+    /// loop ends.
+    pub(crate) fn handle_exiting(&mut self) {
+        if self.shared.app_state.replace(AppState::Exiting) == AppState::Exiting {
+            return;
+        }
+
+        tracing::debug!("The app is exiting");
+        let event = Event::UserEvent(UserWindowEvent::AppStateChanged(AppState::Exiting));
+        self.shared
+            .event_handlers
+            .apply_event(&event, &self.shared.target);
+
+        for handler in self.shared.exit_handlers.take() {
+            handler();
+        }
+    }
+
     /// Do our best to preserve state about the window when the event loop is destroyed
     ///
     /// This will attempt to save the window position, size, and monitor into the environment before
     /// closing. This way, when the app is restarted, it can attempt to restore the window to the same
     /// position and size it was in before, making a better DX.
-    pub(crate) fn handle_loop_destroyed(&self) {
-        #[cfg(debug_assertions)]
+    ///
+    /// Then we drop the windows so component cleanup runs, and give any tasks still alive on the tokio runtime a
+    /// bounded amount of time to wind down. The exit handlers already ran in [`App::handle_exiting`].
+    pub(crate) fn handle_loop_destroyed(&mut self) {
+        tracing::debug!("The event loop is shutting down");
+        self.handle_exiting();
         self.persist_window_state();
-    }
+        self.webviews.clear();
 
-    #[cfg(debug_assertions)]
-    fn persist_window_state(&self) {
-        if let Some(webview) = self.webviews.values().next() {
-            let window = &webview.desktop_context.window;
-
-            let Some(monitor) = window.current_monitor() else {
-                return;
-            };
+        #[cfg(feature = "tokio_runtime")]
+        self.wait_for_runtime_tasks();
+    }
 
-            let Ok(position) = window.outer_position() else {
-                return;
-            };
+    #[cfg(feature = "tokio_runtime")]
+    fn wait_for_runtime_tasks(&self) {
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
 
-            let size = window.outer_size();
+        let deadline = std::time::Instant::now() + self.exit_grace_period;
+        while handle.metrics().num_alive_tasks() > 0 && std::time::Instant::now() < deadline {
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+    }
 
-            let x = position.x;
-            let y = position.y;
+    /// The file the main window's state is saved to, if we should be saving it at all.
+    ///
+    /// In debug mode we always preserve the window state between hot-reload restarts, even if the app didn't opt in.
+    fn window_state_file(&self) -> Option<std::path::PathBuf> {
+        // We only want to do this on desktop
+        if cfg!(target_os = "android") || cfg!(target_os = "ios") {
+            return None;
+        }
 
-            // This is to work around a bug in how tao handles inner_size on macOS
-            // We *want* to use inner_size, but that's currently broken, so we use outer_size instead and then an adjustment
-            //
-            // https://github.com/tauri-apps/tao/issues/889
-            let adjustment = match window.is_decorated() {
-                true if cfg!(target_os = "macos") => 56,
-                _ => 0,
-            };
+        if self.remember_window_state {
+            return window_state_file();
+        }
 
-            let Some(monitor_name) = monitor.name() else {
-                return;
-            };
+        cfg!(debug_assertions).then(restore_file)
+    }
 
-            let state = PreservedWindowState {
-                x,
-                y,
-                width: size.width.max(200),
-                height: size.height.saturating_sub(adjustment).max(200),
-                monitor: monitor_name.to_string(),
-            };
+    fn persist_window_state(&self) {
+        let Some(path) = self.window_state_file() else {
+            return;
+        };
 
-            // Yes... I know... we're loading a file that might not be ours... but it's a debug feature
-            if let Ok(state) = serde_json::to_string(&state) {
-                _ = std::fs::write(restore_file(), state);
-            }
+        if let Some(webview) = self.webviews.values().next() {
+            save_window_state(&webview.desktop_context, &path);
         }
     }
 
-    // Write this to the target dir so we can pick back up
     fn resume_from_state(
         &mut self,
         webview: &WebviewInstance,
         explicit_inner_size: Option<tao::dpi::Size>,
         explicit_window_position: Option<tao::dpi::Position>,
     ) {
-        // We only want to do this on desktop
-        if cfg!(target_os = "android") || cfg!(target_os = "ios") {
+        let Some(path) = self.window_state_file() else {
             return;
-        }
-
-        // We only want to do this in debug mode
-        if !cfg!(debug_assertions) {
-            return;
-        }
-
-        if let Ok(state) = std::fs::read_to_string(restore_file()) {
-            if let Ok(state) = serde_json::from_str::<PreservedWindowState>(&state) {
-                let window = &webview.desktop_context.window;
-                let position = (state.x, state.y);
-                let size = (state.width, state.height);
-
-                // Only set the outer position if it wasn't explicitly set
-                if explicit_window_position.is_none() {
-                    window.set_outer_position(tao::dpi::PhysicalPosition::new(
-                        position.0, position.1,
-                    ));
-                }
+        };
 
-                // Only set the inner size if it wasn't explicitly set
-                if explicit_inner_size.is_none() {
-                    window.set_inner_size(tao::dpi::PhysicalSize::new(size.0, size.1));
-                }
-            }
-        }
+        restore_window_state(
+            &webview.desktop_context,
+            &path,
+            explicit_inner_size,
+            explicit_window_position,
+        );
     }
 
     /// Wire up a receiver to sigkill that lets us preserve the window state
@@ -570,6 +953,159 @@ struct PreservedWindowState {
     width: u32,
     height: u32,
     monitor: String,
+    #[serde(default)]
+    maximized: bool,
+    #[serde(default)]
+    fullscreen: bool,
+    #[serde(default)]
+    zoom: Option<f64>,
+}
+
+/// Save the position, size, maximized/fullscreen state, and zoom level of a window to the given file
+pub(crate) fn save_window_state(desktop: &DesktopService, path: &std::path::Path) {
+    let window = &desktop.window;
+
+    let Some(monitor) = window.current_monitor() else {
+        return;
+    };
+
+    let Ok(position) = window.outer_position() else {
+        return;
+    };
+
+    let size = window.outer_size();
+
+    let x = position.x;
+    let y = position.y;
+
+    // This is to work around a bug in how tao handles inner_size on macOS
+    // We *want* to use inner_size, but that's currently broken, so we use outer_size instead and then an adjustment
+    //
+    // https://github.com/tauri-apps/tao/issues/889
+    let adjustment = match window.is_decorated() {
+        true if cfg!(target_os = "macos") => 56,
+        _ => 0,
+    };
+
+    let Some(monitor_name) = monitor.name() else {
+        return;
+    };
+
+    let maximized = window.is_maximized();
+    let fullscreen = window.fullscreen().is_some();
+
+    let mut state = PreservedWindowState {
+        x,
+        y,
+        width: size.width.max(200),
+        height: size.height.saturating_sub(adjustment).max(200),
+        monitor: monitor_name.to_string(),
+        maximized,
+        fullscreen,
+        zoom: Some(desktop.get_zoom_level()),
+    };
+
+    // A maximized or fullscreen window covers the screen, so keep the bounds it had before to return to when it's
+    // restored. They are only missing if the window was never saved in its normal state.
+    if maximized || fullscreen {
+        if let Some(previous) = std::fs::read_to_string(path)
+            .ok()
+            .and_then(|previous| serde_json::from_str::<PreservedWindowState>(&previous).ok())
+        {
+            state = PreservedWindowState {
+                maximized,
+                fullscreen,
+                zoom: state.zoom,
+                ..previous
+            };
+        }
+    }
+
+    if let Some(dir) = path.parent() {
+        _ = std::fs::create_dir_all(dir);
+    }
+
+    if let Ok(state) = serde_json::to_string(&state) {
+        _ = std::fs::write(path, state);
+    }
+}
+
+/// Restore a window to the state saved in the given file, keeping it on screen if the displays have changed
+///
+/// Returns false if there was no saved state to restore.
+pub(crate) fn restore_window_state(
+    desktop: &DesktopService,
+    path: &std::path::Path,
+    explicit_inner_size: Option<tao::dpi::Size>,
+    explicit_window_position: Option<tao::dpi::Position>,
+) -> bool {
+    let window = &desktop.window;
+
+    let Some(state) = std::fs::read_to_string(path)
+        .ok()
+        .and_then(|state| serde_json::from_str::<PreservedWindowState>(&state).ok())
+    else {
+        return false;
+    };
+
+    let (mut x, mut y) = (state.x, state.y);
+    let (mut width, mut height) = (state.width, state.height);
+
+    // The size constraints may have changed since the state was saved
+    let constraints = desktop.size_constraints.get();
+    let min = constraints.min_size_physical::<u32>(window.scale_factor());
+    let max = constraints.max_size_physical::<u32>(window.scale_factor());
+    width = width.clamp(min.width, max.width.max(min.width));
+    height = height.clamp(min.height, max.height.max(min.height));
+
+    // If the monitor the window was on is gone, fall back to the primary monitor. Either way, shrink the window to
+    // fit the display and pull it back onto the screen. Tao doesn't tell us the work area of a monitor, so this keeps
+    // the window on the whole display and it can still end up partly under the taskbar or dock.
+    let monitor = window
+        .available_monitors()
+        .find(|monitor| monitor.name().as_deref() == Some(state.monitor.as_str()))
+        .or_else(|| window.primary_monitor());
+
+    if let Some(monitor) = monitor {
+        let origin = monitor.position();
+        let bounds = monitor.size();
+
+        width = width.min(bounds.width);
+        height = height.min(bounds.height);
+        x = x.clamp(origin.x, origin.x + (bounds.width - width) as i32);
+        y = y.clamp(origin.y, origin.y + (bounds.height - height) as i32);
+    }
+
+    // Only set the outer position if it wasn't explicitly set
+    if explicit_window_position.is_none() {
+        window.set_outer_position(tao::dpi::PhysicalPosition::new(x, y));
+    }
+
+    // Only set the inner size if it wasn't explicitly set
+    if explicit_inner_size.is_none() {
+        window.set_inner_size(tao::dpi::PhysicalSize::new(width, height));
+    }
+
+    if state.maximized {
+        window.set_maximized(true);
+    }
+
+    if state.fullscreen {
+        window.set_fullscreen(Some(tao::window::Fullscreen::Borderless(None)));
+    }
+
+    if let Some(zoom) = state.zoom {
+        desktop.set_zoom_level(zoom);
+    }
+
+    true
+}
+
+/// The file windows save their state to when the app opts into remembering it
+pub(crate) fn window_state_file() -> Option<std::path::PathBuf> {
+    let exe = std::env::current_exe().ok()?;
+    let app_name = exe.file_stem()?;
+    Some(dirs::config_dir()?.join(app_name).join("window-state.json"))
 }
 
 /// Hide the last window when using LastWindowHides.
@@ -0,0 +1,154 @@
+use wry::WebView;
+
+/// Let the webview load pages with invalid TLS certificates. This needs to be called on the main thread.
+pub(crate) fn ignore_certificate_errors(webview: &WebView) {
+    if !cfg!(debug_assertions) {
+        tracing::error!(
+            "Config::with_ignore_certificate_errors is ignored in release builds. Certificate errors are still checked."
+        );
+        return;
+    }
+
+    tracing::warn!(
+        "Certificate errors are ignored for this window, so its connections are open to interception. Never ship an app \
+        with Config::with_ignore_certificate_errors enabled."
+    );
+
+    if let Err(err) = platform::ignore_certificate_errors(webview) {
+        tracing::error!("Failed to ignore certificate errors: {err}");
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use webview2_com::{
+        Microsoft::Web::WebView2::Win32::{
+            ICoreWebView2_14, COREWEBVIEW2_SERVER_CERTIFICATE_ERROR_ACTION_ALWAYS_ALLOW,
+        },
+        ServerCertificateErrorDetectedEventHandler,
+    };
+    use windows::{core::Interface, Win32::System::WinRT::EventRegistrationToken};
+    use wry::{WebView, WebViewExtWindows};
+
+    pub(super) fn ignore_certificate_errors(webview: &WebView) -> Result<(), String> {
+        let core = unsafe { webview.controller().CoreWebView2() }
+            .and_then(|core| core.cast::<ICoreWebView2_14>())
+            .map_err(|_| "the installed WebView2 runtime is too old".to_string())?;
+
+        let handler = ServerCertificateErrorDetectedEventHandler::create(Box::new(|_, args| {
+            if let Some(args) = args {
+                unsafe {
+                    args.SetAction(COREWEBVIEW2_SERVER_CERTIFICATE_ERROR_ACTION_ALWAYS_ALLOW)?
+                };
+            }
+            Ok(())
+        }));
+
+        let mut token = EventRegistrationToken::default();
+        unsafe { core.add_ServerCertificateErrorDetected(&handler, &mut token) }
+            .map_err(|err| err.message().to_string())
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use webkit2gtk::{TLSErrorsPolicy, WebViewExt, WebsiteDataManagerExt};
+    use wry::{WebView, WebViewExtUnix};
+
+    pub(super) fn ignore_certificate_errors(webview: &WebView) -> Result<(), String> {
+        // Every window has its own web context, so this doesn't leak into the other windows
+        let manager = webview
+            .webview()
+            .website_data_manager()
+            .ok_or_else(|| "the webview has no data manager".to_string())?;
+        manager.set_tls_errors_policy(TLSErrorsPolicy::Ignore);
+        Ok(())
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use block::Block;
+    use objc::{
+        class, msg_send,
+        runtime::{class_addMethod, Class, Object, Sel, BOOL, NO},
+        sel, sel_impl,
+    };
+    use std::ffi::CStr;
+    use wry::{WebView, WebViewExtMacOS};
+
+    /// `NSURLSessionAuthChallengeUseCredential`
+    const USE_CREDENTIAL: isize = 0;
+    /// `NSURLSessionAuthChallengePerformDefaultHandling`
+    const PERFORM_DEFAULT_HANDLING: isize = 1;
+
+    pub(super) fn ignore_certificate_errors(webview: &WebView) -> Result<(), String> {
+        // wry doesn't answer authentication challenges, so we teach its navigation delegate to. The delegate class is
+        // shared by every window, so this applies to all of them once one window asks for it.
+        let class = Class::get("WryNavigationDelegate")
+            .ok_or_else(|| "the navigation delegate of wry was not found".to_string())?;
+
+        let added: BOOL = unsafe {
+            class_addMethod(
+                class as *const Class as *mut Class,
+                sel!(webView:didReceiveAuthenticationChallenge:completionHandler:),
+                std::mem::transmute::<
+                    extern "C" fn(
+                        &Object,
+                        Sel,
+                        *mut Object,
+                        *mut Object,
+                        &Block<(isize, *mut Object), ()>,
+                    ),
+                    objc::runtime::Imp,
+                >(did_receive_challenge),
+                c"v@:@@@?".as_ptr(),
+            )
+        };
+
+        if added == NO {
+            tracing::trace!("The navigation delegate already answers authentication challenges");
+        }
+
+        // WebKit checks which methods the delegate has when it is set, so set it again to pick up the new one
+        unsafe {
+            let webview = webview.webview();
+            let delegate: *mut Object = msg_send![webview, navigationDelegate];
+            let _: () = msg_send![webview, setNavigationDelegate: delegate];
+        }
+
+        Ok(())
+    }
+
+    extern "C" fn did_receive_challenge(
+        _this: &Object,
+        _cmd: Sel,
+        _webview: *mut Object,
+        challenge: *mut Object,
+        completion_handler: &Block<(isize, *mut Object), ()>,
+    ) {
+        unsafe {
+            let space: *mut Object = msg_send![challenge, protectionSpace];
+            let method: *mut Object = msg_send![space, authenticationMethod];
+            let method: *const std::os::raw::c_char = msg_send![method, UTF8String];
+
+            if CStr::from_ptr(method).to_bytes() == b"NSURLAuthenticationMethodServerTrust" {
+                let trust: *mut Object = msg_send![space, serverTrust];
+                let credential: *mut Object =
+                    msg_send![class!(NSURLCredential), credentialForTrust: trust];
+                completion_handler.call((USE_CREDENTIAL, credential));
+            } else {
+                completion_handler.call((PERFORM_DEFAULT_HANDLING, std::ptr::null_mut()));
+            }
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux", target_os = "macos")))]
+mod platform {
+    use wry::WebView;
+
+    pub(super) fn ignore_certificate_errors(_webview: &WebView) -> Result<(), String> {
+        Err("the mobile webviews always check certificates".to_string())
+    }
+}
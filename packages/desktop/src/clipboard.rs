@@ -0,0 +1,34 @@
+/// An image read from the system clipboard with
+/// [`DesktopService::get_clipboard_image`](crate::DesktopService::get_clipboard_image)
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ImageData {
+    /// The width of the image in pixels
+    pub width: usize,
+    /// The height of the image in pixels
+    pub height: usize,
+    /// The pixels row by row from the top left, as 8 bit RGBA with straight (not premultiplied) alpha
+    pub rgba: Vec<u8>,
+}
+
+impl ImageData {
+    /// Convert an image read by arboard, which already reorders the BGRA bitmaps of Windows and decodes the TIFFs of
+    /// macOS into straight RGBA.
+    pub(crate) fn from_arboard(image: arboard::ImageData) -> Self {
+        let mut rgba = image.bytes.into_owned();
+
+        // Bitmaps without an alpha channel, like the screenshots Windows copies, read back with an alpha of 0 for every
+        // pixel. Showing them as fully transparent is never what was copied.
+        if rgba.chunks_exact(4).all(|pixel| pixel[3] == 0) {
+            for pixel in rgba.chunks_exact_mut(4) {
+                pixel[3] = u8::MAX;
+            }
+        }
+
+        Self {
+            width: image.width,
+            height: image.height,
+            rgba,
+        }
+    }
+}
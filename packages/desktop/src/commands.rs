@@ -0,0 +1,105 @@
+use crate::WeakDesktopContext;
+use dioxus_core::prelude::Callback;
+use rustc_hash::FxHashMap;
+use serde::Deserialize;
+use serde_json::Value;
+use std::{cell::RefCell, rc::Rc};
+
+/// The result of a command, which resolves or rejects the promise returned by `window.ipc.invoke` in JavaScript
+pub type CommandResult = Result<Value, String>;
+
+type CommandHandler = Callback<(Value, CommandResponder)>;
+
+/// The commands JavaScript can call in a window with `window.ipc.invoke`
+#[derive(Clone, Default)]
+pub(crate) struct CommandRegistry {
+    handlers: Rc<RefCell<FxHashMap<String, CommandHandler>>>,
+}
+
+impl CommandRegistry {
+    pub fn register_handler(&self, name: String, f: CommandHandler) {
+        self.handlers.borrow_mut().insert(name, f);
+    }
+
+    pub fn remove_handler(&self, name: &str) {
+        self.handlers.borrow_mut().remove(name);
+    }
+
+    /// Run the handler of the command, or reject the call if nothing handles it
+    pub fn invoke(&self, request: InvokeRequest, responder: CommandResponder) {
+        // Copy the handler out so it can register or remove commands while it runs
+        let handler = self.handlers.borrow().get(&request.command).copied();
+        match handler {
+            // And run the handler in the scope of the component that registered it
+            Some(handler) => handler.call((request.args, responder)),
+            None => responder.respond(Err(format!("Unknown command {:?}", request.command))),
+        }
+    }
+}
+
+/// A call of `window.ipc.invoke` carried over IPC
+#[derive(Deserialize)]
+pub(crate) struct InvokeRequest {
+    pub id: u64,
+    pub command: String,
+    #[serde(default)]
+    pub args: Value,
+}
+
+/// Settles the promise of one `window.ipc.invoke` call
+pub(crate) struct CommandResponder {
+    id: u64,
+    desktop: WeakDesktopContext,
+}
+
+impl CommandResponder {
+    pub fn new(id: u64, desktop: WeakDesktopContext) -> Self {
+        Self { id, desktop }
+    }
+
+    pub fn respond(self, result: CommandResult) {
+        // The window may have closed while the command ran
+        let Some(desktop) = self.desktop.upgrade() else {
+            return;
+        };
+
+        let (ok, value) = match result {
+            Ok(value) => (true, value),
+            Err(err) => (false, Value::String(err)),
+        };
+        let script = format!("window.__dioxusSettleInvoke({}, {ok}, {value});", self.id);
+        if let Err(err) = desktop.webview.evaluate_script(&script) {
+            tracing::error!("Failed to send the result of a command to the webview: {err}");
+        }
+    }
+}
+
+/// Adds `window.ipc.invoke`, which calls a command registered with [`use_command`](crate::use_command) and returns a
+/// promise of its result.
+pub(crate) const INVOKE_SCRIPT: &str = r#"
+(function() {
+    let pending = new Map();
+    // Results can still arrive for calls made before the page reloaded, so don't reuse their ids
+    let nextId = Math.floor(Math.random() * 1e9) * 1000;
+    window.ipc.invoke = function(command, args) {
+        let id = nextId++;
+        return new Promise(function(resolve, reject) {
+            pending.set(id, { resolve: resolve, reject: reject });
+            window.ipc.postMessage(JSON.stringify({
+                method: 'invoke',
+                params: { id: id, command: command, args: args === undefined ? null : args },
+            }));
+        });
+    };
+    window.__dioxusSettleInvoke = function(id, ok, value) {
+        let call = pending.get(id);
+        if (call == null) return;
+        pending.delete(id);
+        if (ok) {
+            call.resolve(value);
+        } else {
+            call.reject(new Error(value));
+        }
+    };
+})();
+"#;
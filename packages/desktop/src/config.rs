@@ -1,13 +1,24 @@
 use dioxus_core::LaunchConfig;
 use std::borrow::Cow;
 use std::path::PathBuf;
+use std::rc::Rc;
+#[cfg(feature = "tokio_runtime")]
+use std::time::Duration;
+use tao::dpi::{LogicalSize, Position};
 use tao::event_loop::{EventLoop, EventLoopWindowTarget};
-use tao::window::{Icon, WindowBuilder};
+use tao::window::{Icon, Theme, WindowBuilder};
+use url::Url;
 use wry::http::{Request as HttpRequest, Response as HttpResponse};
 use wry::RequestAsyncResponder;
 
+use crate::accelerators::ActionId;
+use crate::context_menu::{ContextMenuItem, ContextMenuMode};
+use crate::download::DownloadRequest;
+use crate::error_page::NavigationError;
 use crate::ipc::UserWindowEvent;
+use crate::launch::LaunchError;
 use crate::menubar::{default_menu_bar, DioxusMenu};
+use crate::RenderObserver;
 
 type CustomEventHandler = Box<
     dyn 'static
@@ -29,6 +40,221 @@ pub enum WindowCloseBehaviour {
     CloseWindow,
 }
 
+/// What to do when the webview tries to leave the app, for example when a link to a website is clicked.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ExternalLinkPolicy {
+    /// Default behaviour, http, https, and mailto links are opened with the system's default browser or mail client
+    OpenInBrowser,
+    /// External links are ignored
+    Block,
+}
+
+impl ExternalLinkPolicy {
+    /// Handle a url the webview tried to navigate to or open in a new window
+    pub(crate) fn open(self, url: &str) {
+        let is_external = ["http://", "https://", "mailto:"]
+            .iter()
+            .any(|scheme| url.starts_with(scheme));
+
+        if !is_external || self == ExternalLinkPolicy::Block {
+            return;
+        }
+
+        if let Err(e) = webbrowser::open(url) {
+            tracing::error!("Open Browser error: {:?}", e);
+        }
+    }
+}
+
+/// What happens to a navigation away from the app's own page, decided by the handler of
+/// [`Config::with_navigation_policy`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum NavigationDecision {
+    /// Let the webview load the page. Pages that would open in a new window are loaded in this one instead.
+    Allow,
+    /// Cancel the navigation and leave the current page as it is
+    Deny,
+    /// Cancel the navigation and hand the url to the [`ExternalLinkPolicy`] of the window
+    OpenExternally,
+    /// Cancel the navigation and load the page in a new window of the app instead. The new window is tracked like any
+    /// other window, so it closes with the app.
+    ///
+    /// It uses the same navigation policy and [`ExternalLinkPolicy`], and loads pages the same way: with the same
+    /// data directory or incognito mode, proxy, headers, user agent, initialization scripts, certificate checks,
+    /// spellchecking and autoplay policy. Everything else, like the window options, the menu, custom protocols, and the
+    /// content security policy of the app's own page, is left at its default.
+    ///
+    /// Windows opened this way load the pages their policy would open in a new window themselves, so a page can't
+    /// open windows in a loop.
+    OpenInNewWindow,
+}
+
+/// Where a window is placed when it opens, set with [`Config::with_position`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum WindowPosition {
+    /// Center the window on the monitor under the cursor, or on the primary monitor if the cursor can't be found
+    Centered,
+    /// Place the top left corner of the window at the given position
+    At(Position),
+}
+
+/// A native effect drawn behind a transparent window, set with [`Config::with_background_effect`].
+///
+/// Windows uses the DWM backdrops and macOS uses an `NSVisualEffectView`. Linux has no equivalent, so the window is just
+/// transparent there.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum BlurMode {
+    /// A plain blur of whatever is behind the window. On macOS this is the window background material.
+    Blur,
+    /// The acrylic backdrop of Windows 10 and 11. On macOS this is the HUD window material.
+    Acrylic,
+    /// The mica backdrop of Windows 11. On macOS this is the under window background material.
+    Mica,
+}
+
+/// When `<audio>` and `<video>` elements may start playing on their own, set with [`Config::with_autoplay_policy`].
+///
+/// Not every webview can be configured this finely:
+///
+/// - WebView2 on Windows honors all three.
+/// - WebKitGTK on Linux honors [`AutoplayPolicy::AllowAll`]. The other two both use its own policy, which lets muted
+///   media play.
+/// - WKWebView on macOS and iOS honors [`AutoplayPolicy::AllowAll`]. The other two both use its own policy, which on
+///   iOS waits for the user for all media.
+/// - The Android webview honors [`AutoplayPolicy::AllowAll`]. The other two both wait for the user for all media.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum AutoplayPolicy {
+    /// Media only plays after the user interacted with the page, like in a browser
+    RequireUserGesture,
+    /// Muted media can play on its own, media with sound waits for the user
+    AllowMutedOnly,
+    /// All media can play on its own. This is the default, since it's what dioxus windows have always done.
+    #[default]
+    AllowAll,
+}
+
+/// A proxy the webview sends its traffic through, set with [`Config::with_proxy`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProxyConfig {
+    pub(crate) kind: ProxyKind,
+    pub(crate) host: String,
+    pub(crate) port: u16,
+    pub(crate) credentials: Option<(String, String)>,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub(crate) enum ProxyKind {
+    Http,
+    Socks5,
+}
+
+impl ProxyConfig {
+    /// Connect through an HTTP proxy with `CONNECT`
+    pub fn http(host: impl Into<String>, port: u16) -> Self {
+        Self::new(ProxyKind::Http, host.into(), port)
+    }
+
+    /// Connect through a SOCKSv5 proxy
+    pub fn socks5(host: impl Into<String>, port: u16) -> Self {
+        Self::new(ProxyKind::Socks5, host.into(), port)
+    }
+
+    fn new(kind: ProxyKind, host: String, port: u16) -> Self {
+        Self {
+            kind,
+            host,
+            port,
+            credentials: None,
+        }
+    }
+
+    /// Log in to the proxy with a username and password. This is only supported on Linux.
+    pub fn with_credentials(
+        mut self,
+        username: impl Into<String>,
+        password: impl Into<String>,
+    ) -> Self {
+        self.credentials = Some((username.into(), password.into()));
+        self
+    }
+
+    /// Check that the webview of this platform can use the proxy
+    pub(crate) fn check_supported(&self) -> Result<(), LaunchError> {
+        if cfg!(any(target_os = "android", target_os = "ios")) {
+            return Err(LaunchError::ProxyUnsupported {
+                reason: "the mobile webviews can't be configured with a proxy",
+            });
+        }
+
+        if cfg!(all(target_os = "macos", not(feature = "mac-proxy"))) {
+            return Err(LaunchError::ProxyUnsupported {
+                reason: "proxies on macOS need macOS 14 and the `mac-proxy` feature",
+            });
+        }
+
+        if self.credentials.is_some() && !cfg!(target_os = "linux") {
+            return Err(LaunchError::ProxyUnsupported {
+                reason: "only the Linux webview can log in to a proxy",
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Convert to the proxy config of wry, which only knows about hosts and ports
+    pub(crate) fn to_wry(&self) -> wry::ProxyConfig {
+        // webkit2gtk reads the credentials out of the proxy url, which wry builds from the host
+        let host = match &self.credentials {
+            Some((username, password)) => format!(
+                "{}:{}@{}",
+                urlencoding::encode(username),
+                urlencoding::encode(password),
+                self.host
+            ),
+            None => self.host.clone(),
+        };
+
+        let endpoint = wry::ProxyEndpoint {
+            host,
+            port: self.port.to_string(),
+        };
+
+        match self.kind {
+            ProxyKind::Http => wry::ProxyConfig::Http(endpoint),
+            ProxyKind::Socks5 => wry::ProxyConfig::Socks5(endpoint),
+        }
+    }
+}
+
+/// The tokio runtime the desktop renderer drives async tasks on.
+#[cfg(feature = "tokio_runtime")]
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub enum RuntimeFlavor {
+    /// Default behaviour, a multi-threaded runtime. If `worker_threads` is `None`, tokio uses one worker per core.
+    MultiThread {
+        /// The number of worker threads the runtime spawns
+        worker_threads: Option<usize>,
+    },
+    /// A single-threaded runtime.
+    ///
+    /// The main thread is blocked by the event loop, so the runtime is driven from one background thread instead of
+    /// a pool of workers. `tokio::spawn` still works from components, but every task shares that one thread, so
+    /// avoid blocking inside of tasks.
+    CurrentThread,
+    /// Use a runtime that already exists instead of starting a new one.
+    ///
+    /// This is useful when the app is one part of a larger program that already runs tokio. The VirtualDom still
+    /// lives on the main thread with the event loop, but any tasks spawned with `tokio::spawn` run on the provided
+    /// runtime.
+    External(tokio::runtime::Handle),
+}
+
 /// The state of the menu builder. We need to keep track of if the state is default
 /// so we only swap out the default menu bar when decorations are disabled
 pub(crate) enum MenuBuilderState {
@@ -55,23 +281,123 @@ pub struct Config {
     pub(crate) asynchronous_protocols: Vec<AsyncWryProtocol>,
     pub(crate) pre_rendered: Option<String>,
     pub(crate) disable_context_menu: bool,
+    pub(crate) context_menu: Option<Vec<ContextMenuItem>>,
     pub(crate) resource_dir: Option<PathBuf>,
     pub(crate) data_dir: Option<PathBuf>,
+    pub(crate) incognito: bool,
     pub(crate) custom_head: Option<String>,
     pub(crate) custom_index: Option<String>,
     pub(crate) root_name: String,
     pub(crate) background_color: Option<(u8, u8, u8, u8)>,
     pub(crate) last_window_close_behavior: WindowCloseBehaviour,
+    pub(crate) exit_app_on_close: Option<bool>,
+    pub(crate) remember_window_state: bool,
+    pub(crate) open_devtools: bool,
+    pub(crate) init_scripts: Vec<String>,
+    pub(crate) external_link_policy: ExternalLinkPolicy,
+    pub(crate) navigation_policy: Option<NavigationPolicy>,
+    /// If the window was opened for [`NavigationDecision::OpenInNewWindow`], in which case it loads those pages itself
+    pub(crate) opened_for_navigation: bool,
+    pub(crate) disable_drag_drop_handler: bool,
+    pub(crate) zoom_hotkeys: bool,
+    pub(crate) user_agent: Option<String>,
+    pub(crate) proxy: Option<ProxyConfig>,
+    pub(crate) headers: Vec<(String, String)>,
+    pub(crate) accept_language: Option<String>,
+    pub(crate) strict_ipc: bool,
+    pub(crate) spellcheck: Option<bool>,
+    pub(crate) autoplay_policy: AutoplayPolicy,
+    pub(crate) ignore_certificate_errors: bool,
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    pub(crate) accelerators: Vec<(muda::accelerator::Accelerator, ActionId)>,
+    pub(crate) csp: Option<String>,
+    pub(crate) download_handler: Option<DownloadHandler>,
+    pub(crate) error_page: Option<ErrorPageHandler>,
+    pub(crate) background_effect: Option<BlurMode>,
+    pub(crate) corner_radius: Option<f32>,
+    pub(crate) window_shadow: bool,
+    pub(crate) centered: bool,
+    pub(crate) skip_taskbar: bool,
+    pub(crate) render_observer: Option<Rc<dyn RenderObserver>>,
     pub(crate) custom_event_handler: Option<CustomEventHandler>,
+    pub(crate) exit_handler: Option<Box<dyn FnOnce()>>,
+    #[cfg(feature = "tokio_runtime")]
+    pub(crate) exit_grace_period: Duration,
+    #[cfg(feature = "tokio_runtime")]
+    pub(crate) runtime_flavor: RuntimeFlavor,
 }
 
 impl LaunchConfig for Config {}
 
+/// The headers browsers don't let pages set, from <https://fetch.spec.whatwg.org/#forbidden-request-header>
+fn is_forbidden_header(name: &wry::http::HeaderName) -> bool {
+    const FORBIDDEN: &[&str] = &[
+        "accept-charset",
+        "accept-encoding",
+        "access-control-request-headers",
+        "access-control-request-method",
+        "connection",
+        "content-length",
+        "cookie",
+        "cookie2",
+        "date",
+        "dnt",
+        "expect",
+        "host",
+        "keep-alive",
+        "origin",
+        "referer",
+        "set-cookie",
+        "te",
+        "trailer",
+        "transfer-encoding",
+        "upgrade",
+        "via",
+    ];
+
+    // Header names are always lowercase once parsed
+    let name = name.as_str();
+    FORBIDDEN.contains(&name) || name.starts_with("proxy-") || name.starts_with("sec-")
+}
+
+/// A Content Security Policy that works with the bundled interpreter and only allows content served by the app itself.
+///
+/// Pass it to [`Config::with_csp`], or use it as a starting point for your own policy.
+pub const DEFAULT_CSP: &str = "default-src 'self' dioxus: http://dioxus.index.html; \
+    script-src 'self' dioxus: http://dioxus.index.html 'nonce-{nonce}' 'unsafe-eval'; \
+    style-src 'self' dioxus: http://dioxus.index.html 'unsafe-inline'; \
+    img-src 'self' dioxus: http://dioxus.index.html data: blob:; \
+    object-src 'none'; \
+    base-uri 'none'";
+
 pub(crate) type WryProtocol = (
     String,
     Box<dyn Fn(HttpRequest<Vec<u8>>) -> HttpResponse<Cow<'static, [u8]>> + 'static>,
 );
 
+pub(crate) type DownloadHandler = Box<dyn FnMut(DownloadRequest) -> Option<PathBuf>>;
+pub(crate) type NavigationPolicy = Rc<dyn Fn(&Url) -> NavigationDecision>;
+pub(crate) type ErrorPageHandler = Box<dyn Fn(NavigationError) -> String>;
+
+/// The settings of a window that decide how the pages it opens in new windows load, so a page opened from an
+/// incognito or proxied window isn't loaded with the default profile and a direct connection
+#[derive(Clone)]
+pub(crate) struct OpenedWindowSettings {
+    external_link_policy: ExternalLinkPolicy,
+    navigation_policy: Option<NavigationPolicy>,
+    data_dir: Option<PathBuf>,
+    incognito: bool,
+    user_agent: Option<String>,
+    proxy: Option<ProxyConfig>,
+    headers: Vec<(String, String)>,
+    accept_language: Option<String>,
+    init_scripts: Vec<String>,
+    ignore_certificate_errors: bool,
+    spellcheck: Option<bool>,
+    autoplay_policy: AutoplayPolicy,
+    strict_ipc: bool,
+}
+
 pub(crate) type AsyncWryProtocol = (
     String,
     Box<dyn Fn(HttpRequest<Vec<u8>>, RequestAsyncResponder) + 'static>,
@@ -100,14 +426,51 @@ impl Config {
             asynchronous_protocols: Vec::new(),
             pre_rendered: None,
             disable_context_menu: !cfg!(debug_assertions),
+            context_menu: None,
             resource_dir: None,
             data_dir: None,
+            incognito: false,
             custom_head: None,
             custom_index: None,
             root_name: "main".to_string(),
             background_color: None,
             last_window_close_behavior: WindowCloseBehaviour::LastWindowExitsApp,
+            exit_app_on_close: None,
+            remember_window_state: false,
+            open_devtools: false,
+            init_scripts: Vec::new(),
+            external_link_policy: ExternalLinkPolicy::OpenInBrowser,
+            navigation_policy: None,
+            opened_for_navigation: false,
+            disable_drag_drop_handler: false,
+            zoom_hotkeys: false,
+            user_agent: None,
+            proxy: None,
+            headers: Vec::new(),
+            accept_language: None,
+            strict_ipc: false,
+            spellcheck: None,
+            autoplay_policy: AutoplayPolicy::AllowAll,
+            ignore_certificate_errors: false,
+            #[cfg(not(any(target_os = "ios", target_os = "android")))]
+            accelerators: Vec::new(),
+            csp: None,
+            download_handler: None,
+            error_page: None,
+            background_effect: None,
+            corner_radius: None,
+            window_shadow: true,
+            centered: false,
+            skip_taskbar: false,
+            render_observer: None,
             custom_event_handler: None,
+            exit_handler: None,
+            #[cfg(feature = "tokio_runtime")]
+            exit_grace_period: Duration::ZERO,
+            #[cfg(feature = "tokio_runtime")]
+            runtime_flavor: RuntimeFlavor::MultiThread {
+                worker_threads: None,
+            },
         }
     }
 
@@ -119,15 +482,187 @@ impl Config {
 
     /// set the directory where data will be stored in release mode.
     ///
+    /// The webview keeps its profile here, so cookies, localStorage, and IndexedDB survive restarts of the app. If the
+    /// directory can't be created or written to, an error is logged and the webview falls back to its default store.
+    ///
     /// > Note: This **must** be set when bundling on Windows.
     pub fn with_data_directory(mut self, path: impl Into<PathBuf>) -> Self {
         self.data_dir = Some(path.into());
         self
     }
 
+    /// Keep cookies, the cache, localStorage, and IndexedDB in memory only, so nothing the page stores is left behind
+    /// once the window closes.
+    ///
+    /// WebKitGTK uses an ephemeral context, WKWebView a non-persistent data store, and WebView2 InPrivate mode. WebView2
+    /// needs version 101 or newer for this, and still keeps the files of its browser process in its default folder.
+    /// Launching fails with [`LaunchError::IncognitoUnsupported`] on Android, and when a data directory is set with
+    /// [`Config::with_data_directory`], since the window would never use it.
+    ///
+    /// This only covers the webview. Files the app writes itself, like the window state of
+    /// [`Config::with_remember_window_state`], are still saved.
+    pub fn with_incognito(mut self, incognito: bool) -> Self {
+        self.incognito = incognito;
+        self
+    }
+
+    /// The settings that windows opened with [`NavigationDecision::OpenInNewWindow`] take from this window
+    pub(crate) fn opened_window_settings(&self) -> OpenedWindowSettings {
+        OpenedWindowSettings {
+            external_link_policy: self.external_link_policy,
+            navigation_policy: self.navigation_policy.clone(),
+            data_dir: self.data_dir.clone(),
+            incognito: self.incognito,
+            user_agent: self.user_agent.clone(),
+            proxy: self.proxy.clone(),
+            headers: self.headers.clone(),
+            accept_language: self.accept_language.clone(),
+            init_scripts: self.init_scripts.clone(),
+            ignore_certificate_errors: self.ignore_certificate_errors,
+            spellcheck: self.spellcheck,
+            autoplay_policy: self.autoplay_policy,
+            strict_ipc: self.strict_ipc,
+        }
+    }
+
+    /// A config for a window opened with [`NavigationDecision::OpenInNewWindow`] by a window with these settings
+    pub(crate) fn for_opened_window(settings: OpenedWindowSettings) -> Self {
+        let mut cfg = Config::new();
+        cfg.external_link_policy = settings.external_link_policy;
+        cfg.navigation_policy = settings.navigation_policy;
+        cfg.data_dir = settings.data_dir;
+        cfg.incognito = settings.incognito;
+        cfg.user_agent = settings.user_agent;
+        cfg.proxy = settings.proxy;
+        cfg.headers = settings.headers;
+        cfg.accept_language = settings.accept_language;
+        cfg.init_scripts = settings.init_scripts;
+        cfg.ignore_certificate_errors = settings.ignore_certificate_errors;
+        cfg.spellcheck = settings.spellcheck;
+        cfg.autoplay_policy = settings.autoplay_policy;
+        cfg.strict_ipc = settings.strict_ipc;
+        cfg.opened_for_navigation = true;
+        cfg
+    }
+
+    /// Check everything about the config that would keep a window from being created with it
+    pub(crate) fn validate(&self) -> Result<(), LaunchError> {
+        if let Some(proxy) = &self.proxy {
+            proxy.check_supported()?;
+        }
+        self.check_incognito()?;
+        self.check_index()?;
+        self.check_background_effect()?;
+        self.header_map()?;
+        Ok(())
+    }
+
+    /// Check that the background effect can show through the window
+    pub(crate) fn check_background_effect(&self) -> Result<(), LaunchError> {
+        if self.background_effect.is_none() {
+            return Ok(());
+        }
+
+        if cfg!(target_os = "macos") && !cfg!(feature = "transparent") {
+            return Err(LaunchError::BackgroundEffectUnsupported {
+                reason:
+                    "background effects on macOS need the `transparent` feature of dioxus-desktop",
+            });
+        }
+
+        if let Some((.., 255)) = self.background_color {
+            return Err(LaunchError::BackgroundEffectUnsupported {
+                reason: "it can't show through an opaque background color, give the background color an alpha below 255",
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Check that the custom index has the tags the loader and the head are inserted into, and the root element
+    pub(crate) fn check_index(&self) -> Result<(), LaunchError> {
+        let Some(index) = &self.custom_index else {
+            return Ok(());
+        };
+
+        let invalid = |reason: String| Err(LaunchError::InvalidIndex { reason });
+        if !index.contains("</body>") {
+            return invalid("it has no </body> tag to insert the interpreter before".to_string());
+        }
+        if self.custom_head.is_some() && !index.contains("</head>") {
+            return invalid("it has no </head> tag to insert the custom head before".to_string());
+        }
+        if self.csp.is_some() && crate::protocol::find_head_open(index).is_none() {
+            return invalid(
+                "it has no <head> tag to insert the content security policy into".to_string(),
+            );
+        }
+
+        let root = &self.root_name;
+        let has_root = [format!(r#"id="{root}""#), format!("id='{root}'")]
+            .iter()
+            .any(|attribute| index.contains(attribute.as_str()));
+        if !has_root {
+            return invalid(format!(
+                r#"it has no element with id="{root}" to render into"#
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Check that the webview of this platform can run incognito with the rest of the config
+    pub(crate) fn check_incognito(&self) -> Result<(), LaunchError> {
+        if !self.incognito {
+            return Ok(());
+        }
+
+        if cfg!(target_os = "android") {
+            return Err(LaunchError::IncognitoUnsupported {
+                reason: "the Android webview can't be made incognito",
+            });
+        }
+
+        if self.data_dir.is_some() {
+            return Err(LaunchError::IncognitoUnsupported {
+                reason: "incognito windows don't store anything in the data directory",
+            });
+        }
+
+        Ok(())
+    }
+
     /// Set whether or not the right-click context menu should be disabled.
     pub fn with_disable_context_menu(mut self, disable: bool) -> Self {
         self.disable_context_menu = disable;
+        self.context_menu = None;
+        self
+    }
+
+    /// Set what happens when the user right-clicks the page.
+    ///
+    /// By default the webview's own menu is shown in debug builds and no menu is shown in release builds. With
+    /// [`ContextMenuMode::Custom`] a native menu with your own items is shown instead, and clicks are delivered to
+    /// [`use_context_menu_handler`](crate::use_context_menu_handler) along with the element that was right-clicked.
+    ///
+    /// Native context menus are only available on Windows, macOS, and Linux. On other platforms a custom menu behaves
+    /// like [`ContextMenuMode::Disabled`], so listen for the `oncontextmenu` event in your components if you need a
+    /// menu there.
+    pub fn with_context_menu(mut self, mode: ContextMenuMode) -> Self {
+        match mode {
+            ContextMenuMode::Disabled => {
+                self.disable_context_menu = true;
+                self.context_menu = None;
+            }
+            ContextMenuMode::Default => {
+                self.disable_context_menu = false;
+                self.context_menu = None;
+            }
+            ContextMenuMode::Custom(items) => {
+                self.disable_context_menu = true;
+                self.context_menu = Some(items);
+            }
+        }
         self
     }
 
@@ -166,6 +701,268 @@ impl Config {
         self
     }
 
+    /// Sets whether closing this window exits the app, regardless of how many other windows are open.
+    ///
+    /// By default windows follow [`Config::with_close_behaviour`]. In a multi-window app you can use this to make
+    /// closing the main window quit the app while closing an auxiliary window with `false` only closes that window.
+    pub fn with_exit_app_on_close(mut self, exit: bool) -> Self {
+        self.exit_app_on_close = Some(exit);
+        self
+    }
+
+    /// Remember the size, position, and maximized/fullscreen state of the main window between launches.
+    ///
+    /// The state is saved to a JSON file in the platform's config directory when the app closes and restored before
+    /// the window is first shown. Explicitly configured window sizes and positions take precedence over saved ones.
+    pub fn with_remember_window_state(mut self, remember: bool) -> Self {
+        self.remember_window_state = remember;
+        self
+    }
+
+    /// Open the webview inspector as soon as the window is created. This only has an effect in debug builds.
+    pub fn with_devtools_open_on_launch(mut self, open: bool) -> Self {
+        self.open_devtools = open;
+        self
+    }
+
+    /// Add a script that runs at the start of every page load, before the Dioxus interpreter boots.
+    ///
+    /// Scripts run in the order they were added, before any script in the index page, so globals they define are
+    /// available to the interpreter and to anything it evaluates. This can be called multiple times.
+    pub fn with_init_script(mut self, script: impl Into<String>) -> Self {
+        self.init_scripts.push(script.into());
+        self
+    }
+
+    /// Sets what happens when the webview tries to navigate away from the app or open a new window.
+    ///
+    /// Navigation within the app itself is always allowed.
+    pub fn with_external_link_policy(mut self, policy: ExternalLinkPolicy) -> Self {
+        self.external_link_policy = policy;
+        self
+    }
+
+    /// Decide what happens to each navigation away from the app's own page, for example to keep a kiosk on its own
+    /// content or to let trusted sites load in the window.
+    ///
+    /// The handler runs before the webview starts loading, for clicked links, `location.href` and other navigations
+    /// from JavaScript, form submissions, and links that open a new window. Urls that can't be parsed are denied
+    /// without asking it. Without a policy, every navigation away from the app is handed to the [`ExternalLinkPolicy`].
+    ///
+    /// The webview never opens windows on its own: pages that `window.open` a url or have `target="_blank"` links
+    /// only get a new window if the policy returns [`NavigationDecision::OpenInNewWindow`]. Opening the app's own pages
+    /// in a new window is denied, since they only resolve inside of the webview. Use
+    /// [`DesktopService::new_window`](crate::DesktopService::new_window) to open another window of the app.
+    ///
+    /// Only the page of the window itself is covered: navigations inside iframes aren't on Windows, macOS, and iOS.
+    /// Android doesn't report form submissions that use `POST`.
+    pub fn with_navigation_policy(
+        mut self,
+        policy: impl Fn(&Url) -> NavigationDecision + 'static,
+    ) -> Self {
+        self.navigation_policy = Some(Rc::new(policy));
+        self
+    }
+
+    /// Disable the native file drag-and-drop handler and let the webview handle drag-and-drop like a browser would.
+    ///
+    /// The native handler gives drop events the real paths of the dropped files and powers
+    /// [`use_file_drop_handler`](crate::use_file_drop_handler), but on some platforms (notably Windows) it stops the
+    /// webview from firing HTML5 drag-and-drop events for anything dragged in from outside the window.
+    pub fn with_disable_drag_drop_handler(mut self, disable: bool) -> Self {
+        self.disable_drag_drop_handler = disable;
+        self
+    }
+
+    /// Let the user zoom the webview with Ctrl/Cmd + Plus, Ctrl/Cmd + Minus, and Ctrl/Cmd + 0.
+    pub fn with_zoom_hotkeys(mut self, enabled: bool) -> Self {
+        self.zoom_hotkeys = enabled;
+        self
+    }
+
+    /// Set the user agent the webview sends with every request, including fetch and XHR requests made by the page.
+    ///
+    /// On Windows this requires WebView2 Runtime 86.0.616.0 or newer. Older runtimes ignore it and keep the default
+    /// user agent.
+    pub fn with_user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Send the traffic of the webview through a proxy.
+    ///
+    /// HTTP and SOCKSv5 proxies are supported on Windows and Linux, and on macOS 14 and newer with the `mac-proxy`
+    /// feature. Launching fails with [`LaunchError::ProxyUnsupported`] on other platforms, and when the proxy needs
+    /// credentials anywhere but Linux, so traffic never silently bypasses the proxy.
+    pub fn with_proxy(mut self, proxy: ProxyConfig) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    /// Send extra HTTP headers, like `Authorization`, with every page the window loads.
+    ///
+    /// The headers are sent when the webview loads a document: the app's own page and pages opened with
+    /// [`DesktopService::load_url`](crate::DesktopService::load_url). Requests the page makes itself, like images,
+    /// scripts and `fetch` calls, don't get them, since not every platform lets us intercept those.
+    ///
+    /// Launching fails with [`LaunchError::InvalidHeader`] if a header isn't valid HTTP or is one the webview sets
+    /// itself, like `Host` or `Cookie`.
+    pub fn with_headers(mut self, headers: Vec<(String, String)>) -> Self {
+        self.headers = headers;
+        self
+    }
+
+    /// Ask for content in these languages with an `Accept-Language` header, like `"de-DE,de;q=0.9,en;q=0.5"`.
+    ///
+    /// The header is sent like the headers of [`Config::with_headers`], with every document the window loads, and
+    /// replaces an `Accept-Language` header set there. On Linux the webview also sends it with the requests the page
+    /// makes itself, and reports the languages in `navigator.languages`. Elsewhere those keep the languages of the OS.
+    ///
+    /// [`DesktopService::preferred_languages`](crate::DesktopService::preferred_languages) gets the languages of the
+    /// OS, to build the header from or to pick the language of the app.
+    pub fn with_accept_language(mut self, accept_language: impl Into<String>) -> Self {
+        self.accept_language = Some(accept_language.into());
+        self
+    }
+
+    /// Turn spellchecking in text fields on or off. Without this, each webview keeps its own default: on for WebView2
+    /// and WKWebView, off for WebKitGTK.
+    ///
+    /// WebKitGTK can toggle spellchecking for the whole webview. WebView2 and WKWebView can't, so there the page's root
+    /// element gets a `spellcheck` attribute, which every element inherits unless it sets its own. Giving an element
+    /// `spellcheck: true` re-enables it there, except on Linux, where the webview's switch wins. The mobile webviews
+    /// follow the keyboard's settings, so this only changes the attribute there.
+    ///
+    /// Use [`DesktopService::set_spellcheck`](crate::DesktopService::set_spellcheck) to change it while the app runs.
+    pub fn with_spellcheck(mut self, enabled: bool) -> Self {
+        self.spellcheck = Some(enabled);
+        self
+    }
+
+    /// Set when media may start playing without the user interacting with the page. See [`AutoplayPolicy`] for what each
+    /// platform supports.
+    ///
+    /// On Windows the policy is passed to WebView2 when it starts, and windows that share a data directory must start it
+    /// the same way, so give every window of the app the same policy.
+    pub fn with_autoplay_policy(mut self, policy: AutoplayPolicy) -> Self {
+        self.autoplay_policy = policy;
+        self
+    }
+
+    /// Log the messages the webview of this window sends that can't be understood as errors instead of warnings.
+    ///
+    /// This makes a custom JavaScript bridge or interpreter that doesn't match dioxus-desktop stand out as soon as it
+    /// happens. The messages are still dropped rather than stopping the app, and
+    /// [`DesktopService::dropped_ipc_messages`](crate::DesktopService::dropped_ipc_messages) counts them either way.
+    pub fn with_strict_ipc(mut self, strict: bool) -> Self {
+        self.strict_ipc = strict;
+        self
+    }
+
+    /// Convert the headers set with [`Config::with_headers`] and [`Config::with_accept_language`] to the header map wry
+    /// sends
+    pub(crate) fn header_map(&self) -> Result<wry::http::HeaderMap, LaunchError> {
+        use wry::http::{HeaderName, HeaderValue};
+
+        let mut map = wry::http::HeaderMap::new();
+        for (name, value) in &self.headers {
+            let invalid = |reason| LaunchError::InvalidHeader {
+                name: name.clone(),
+                reason,
+            };
+
+            let header = HeaderName::try_from(name.as_str())
+                .map_err(|_| invalid("it isn't a valid header name"))?;
+            if is_forbidden_header(&header) {
+                return Err(invalid("the webview sets it itself"));
+            }
+            let value = HeaderValue::try_from(value.as_str())
+                .map_err(|_| invalid("its value isn't a valid header value"))?;
+
+            map.append(header, value);
+        }
+
+        if let Some(accept_language) = &self.accept_language {
+            let value = HeaderValue::try_from(accept_language.as_str()).map_err(|_| {
+                LaunchError::InvalidHeader {
+                    name: "Accept-Language".to_string(),
+                    reason: "its value isn't a valid header value",
+                }
+            })?;
+            map.insert(wry::http::header::ACCEPT_LANGUAGE, value);
+        }
+
+        Ok(map)
+    }
+
+    /// Let the webview load pages whose TLS certificates are invalid, like a local backend with a self-signed certificate.
+    ///
+    /// **This turns off the protection against intercepted connections and must never ship enabled.** It only takes
+    /// effect in debug builds and logs a warning when a window is created. Release builds log an error and keep checking
+    /// certificates. On macOS this applies to every window once one window enables it. The mobile webviews always
+    /// check certificates.
+    pub fn with_ignore_certificate_errors(mut self, ignore: bool) -> Self {
+        self.ignore_certificate_errors = ignore;
+        self
+    }
+
+    /// Lock the page down with a [Content Security Policy](https://developer.mozilla.org/en-US/docs/Web/HTTP/CSP).
+    ///
+    /// The policy is added to the index page as a `<meta http-equiv="Content-Security-Policy">` tag before any script.
+    /// The interpreter is bootstrapped with inline scripts. They are only allowed to run if the policy includes
+    /// `'nonce-{nonce}'` in its `script-src` directive. `{nonce}` is replaced with a fresh random nonce every time the
+    /// page loads, and the bootstrap scripts carry that nonce.
+    ///
+    /// Inline `<script>` tags added with [`Config::with_custom_head`] or a custom index don't get the nonce, so the
+    /// policy blocks them. Serve those scripts as assets, or allow them with a hash in `script-src`.
+    ///
+    /// `eval` and `use_eval` create functions from strings, so they need `'unsafe-eval'`. Dioxus sets some styles with the
+    /// `style` attribute, so `style-src` needs `'unsafe-inline'`. [`DEFAULT_CSP`] meets all of these requirements, and
+    /// it only allows content served by the app itself.
+    pub fn with_csp(mut self, csp: impl Into<String>) -> Self {
+        self.csp = Some(csp.into());
+        self
+    }
+
+    /// Decide where files downloaded by the page are saved.
+    ///
+    /// The handler is called when the page starts a download, from a `download` link, a blob url, or a response with a
+    /// `Content-Disposition` header. Return the absolute path to save the file to, or `None` to cancel the download.
+    /// Destinations that are relative or contain `..` are rejected. The file itself is downloaded by the webview in the
+    /// background, but the handler runs on the event loop, so it shouldn't block.
+    ///
+    /// Use [`use_download_completed_handler`](crate::use_download_completed_handler) to find out when a download
+    /// finishes.
+    pub fn with_download_handler(
+        mut self,
+        handler: impl FnMut(DownloadRequest) -> Option<PathBuf> + 'static,
+    ) -> Self {
+        self.download_handler = Some(Box::new(handler));
+        self
+    }
+
+    /// Show your own page when the webview fails to load one, like a remote page while the network is down.
+    ///
+    /// The handler gets the url and error of the failed page and returns the html to show in its place. The app's own
+    /// page is served from memory, so this mostly matters for pages opened with
+    /// [`DesktopService::load_url`](crate::DesktopService::load_url). The virtual dom isn't rendered into the error
+    /// page unless the html loads the interpreter.
+    ///
+    /// This is supported on Windows and Linux. The other platforms keep the error page of their webview.
+    pub fn with_error_page(
+        mut self,
+        handler: impl Fn(NavigationError) -> String + 'static,
+    ) -> Self {
+        self.error_page = Some(Box::new(handler));
+        self
+    }
+
+    /// Observe how long this window takes to render and to apply edits in the webview.
+    pub fn with_render_observer(mut self, observer: impl RenderObserver + 'static) -> Self {
+        self.render_observer = Some(Rc::new(observer));
+        self
+    }
+
     /// Sets a custom callback to run whenever the event pool receives an event.
     pub fn with_custom_event_handler(
         mut self,
@@ -176,6 +973,34 @@ impl Config {
         self
     }
 
+    /// Sets a callback that runs right before the app exits.
+    ///
+    /// The callback runs once the event loop is shutting down, before the windows and their virtual doms are
+    /// dropped. Components can register their own handlers with [`DesktopService::register_exit_handler`](crate::DesktopService::register_exit_handler).
+    pub fn with_exit_handler(mut self, f: impl FnOnce() + 'static) -> Self {
+        self.exit_handler = Some(Box::new(f));
+        self
+    }
+
+    /// Sets how long the app waits for tasks still running on the tokio runtime to finish before exiting.
+    ///
+    /// The app only waits while there are tasks alive on the runtime. Any task counts, so a task that runs forever, like
+    /// a listener or an interval, makes every exit take the whole grace period. Defaults to zero, so the app doesn't
+    /// wait unless it opts in. With [`RuntimeFlavor::External`] the app doesn't wait at all, since the runtime's tasks
+    /// aren't the app's own.
+    #[cfg(feature = "tokio_runtime")]
+    pub fn with_exit_grace_period(mut self, grace_period: Duration) -> Self {
+        self.exit_grace_period = grace_period;
+        self
+    }
+
+    /// Sets the flavor of the tokio runtime that is started when the app launches.
+    #[cfg(feature = "tokio_runtime")]
+    pub fn with_runtime_flavor(mut self, flavor: RuntimeFlavor) -> Self {
+        self.runtime_flavor = flavor;
+        self
+    }
+
     /// Set a custom protocol
     pub fn with_custom_protocol<F>(mut self, name: impl ToString, handler: F) -> Self
     where
@@ -226,6 +1051,120 @@ impl Config {
         self
     }
 
+    /// Set where the window opens instead of leaving it to the OS.
+    ///
+    /// A position restored with [`Config::with_remember_window_state`] takes priority over centering. Wayland doesn't let
+    /// apps place their windows, so this does nothing there.
+    pub fn with_position(mut self, position: WindowPosition) -> Self {
+        match position {
+            WindowPosition::Centered => self.centered = true,
+            WindowPosition::At(position) => {
+                self.centered = false;
+                self.window = self.window.with_position(position);
+            }
+        }
+        self
+    }
+
+    /// Set the smallest size the user can resize the window to.
+    ///
+    /// A window size restored with [`Config::with_remember_window_state`] is grown to fit.
+    pub fn with_min_inner_size(mut self, size: LogicalSize<f64>) -> Self {
+        self.window = self.window.with_min_inner_size(size);
+        self
+    }
+
+    /// Set the largest size the user can resize the window to.
+    ///
+    /// A window size restored with [`Config::with_remember_window_state`] is shrunk to fit. Some Linux window managers
+    /// ignore the maximum size, and it doesn't stop the window from being maximized. Mobile platforms ignore both limits.
+    pub fn with_max_inner_size(mut self, size: LogicalSize<f64>) -> Self {
+        self.window = self.window.with_max_inner_size(size);
+        self
+    }
+
+    /// Force the window to use a light or dark theme, or follow the OS with `None`.
+    ///
+    /// On Linux and macOS the theme applies to the whole app rather than just this window.
+    pub fn with_theme(mut self, theme: Option<Theme>) -> Self {
+        self.window = self.window.with_theme(theme);
+        self
+    }
+
+    /// Show or hide the window's titlebar and borders.
+    ///
+    /// To build your own titlebar, mark the elements that should move the window with a `data-drag-region` attribute.
+    /// Pressing the element drags the window and double clicking it toggles maximize. Buttons, links, form controls,
+    /// and elements with a `data-no-drag` attribute inside a drag region still work as usual.
+    pub fn with_decorations(mut self, decorations: bool) -> Self {
+        self.window = self.window.with_decorations(decorations);
+        self
+    }
+
+    /// Make the window and the webview transparent, so whatever is behind the window shows through the parts of the
+    /// page without a background.
+    ///
+    /// On macOS the webview can only be transparent with the `transparent` feature, which is enabled by default.
+    /// Without it the page is drawn on an opaque background.
+    pub fn with_transparent(mut self, transparent: bool) -> Self {
+        self.window = self.window.with_transparent(transparent);
+        self
+    }
+
+    /// Draw a native blur effect behind the window. This makes the window transparent.
+    ///
+    /// The effect only shows through parts of the page without a background, and it can't be combined with an opaque
+    /// [`Config::with_background_color`]. The window can't be created if the background color is opaque, or if the
+    /// `transparent` feature is disabled on macOS. Effects the OS version doesn't support are logged and skipped.
+    pub fn with_background_effect(mut self, effect: BlurMode) -> Self {
+        self.background_effect = Some(effect);
+        self.with_transparent(true)
+    }
+
+    /// Round the corners of the window by `radius` logical pixels, mostly for windows without decorations.
+    ///
+    /// - **Windows:** Windows 11 only has small and regular rounded corners, so a radius of 0 turns rounding off,
+    ///   a radius below 6 picks the small corners, and anything larger the regular ones. The shadow follows the
+    ///   corners. Windows 10 has no rounded corners, which is logged.
+    /// - **macOS:** The content of the window is clipped to the radius and the shadow follows it. The corners only
+    ///   show what's behind the window if it's [transparent](Config::with_transparent).
+    /// - **Other platforms:** Nothing happens, since rounding is up to the compositor, which is logged. Use a
+    ///   transparent window and `border-radius` on the root element instead.
+    pub fn with_corner_radius(mut self, radius: f32) -> Self {
+        self.corner_radius = Some(radius);
+        self
+    }
+
+    /// Show or hide the shadow the OS draws around windows without [decorations](Config::with_decorations), which is
+    /// shown by default.
+    ///
+    /// - **Windows:** The shadow comes from the frame DWM extends into the window, which also draws a thin line along
+    ///   the top of it. On transparent windows the shadow surrounds the whole window, not just the visible content.
+    /// - **macOS:** The shadow follows the shape of the visible content, including a
+    ///   [corner radius](Config::with_corner_radius). Decorated windows keep their shadow either way.
+    /// - **Other platforms:** Shadows are up to the compositor, so this does nothing.
+    pub fn with_window_shadow(mut self, shadow: bool) -> Self {
+        self.window_shadow = shadow;
+        self
+    }
+
+    /// Keep the window above every other window, even when it isn't focused.
+    ///
+    /// Debug builds already keep windows on top unless the CLI says otherwise, so pass `false` to opt out.
+    pub fn with_always_on_top(mut self, always_on_top: bool) -> Self {
+        self.window = self.window.with_always_on_top(always_on_top);
+        self
+    }
+
+    /// Leave the window out of the taskbar, which is handy for overlays and HUDs.
+    ///
+    /// macOS has no taskbar button per window, so this hides the whole app from the dock and the app switcher. The app
+    /// then also loses its menu bar. This does nothing on mobile.
+    pub fn with_skip_taskbar(mut self, skip: bool) -> Self {
+        self.skip_taskbar = skip;
+        self
+    }
+
     /// Inject additional content into the document's HEAD.
     ///
     /// This is useful for loading CSS libraries, JS libraries, etc.
@@ -240,6 +1179,11 @@ impl Config {
     ///
     /// Dioxus injects some loader code into the closing body tag. Your document
     /// must include a body element!
+    ///
+    /// The document also needs the element Dioxus renders into, like `<div id="main"></div>` for the default
+    /// [`Config::with_root_name`], and a head element if [`Config::with_custom_head`] or [`Config::with_csp`] is used.
+    /// [`try_launch_virtual_dom`](crate::launch::try_launch_virtual_dom) returns [`LaunchError::InvalidIndex`] if any
+    /// of them is missing, and creating the window panics.
     pub fn with_custom_index(mut self, index: String) -> Self {
         self.custom_index = Some(index);
         self
@@ -253,14 +1197,39 @@ impl Config {
         self
     }
 
-    /// Sets the background color of the WebView.
+    /// Sets the background color of the WebView and the window behind it.
     /// This will be set before the HTML is rendered and can be used to prevent flashing when the page loads.
     /// Accepts a color in RGBA format
+    ///
+    /// On transparent windows, an alpha below 255 lets the desktop show through. Windows ignores the alpha of window
+    /// colors, so there only the webview is colored on transparent windows.
     pub fn with_background_color(mut self, color: (u8, u8, u8, u8)) -> Self {
         self.background_color = Some(color);
         self
     }
 
+    /// Set keyboard shortcuts that trigger actions while this window is focused.
+    ///
+    /// Unlike keydown handlers in the page, these are handled by the OS menu system, so they work while a text input is
+    /// focused and catch combinations the webview would otherwise swallow. The actions are delivered to
+    /// [`use_accelerator_handler`](crate::use_accelerator_handler).
+    ///
+    /// The accelerators are installed as a "Shortcuts" submenu of the window's menu bar, so the window needs one.
+    /// Conflicts are resolved in this order:
+    /// - If the table binds an accelerator twice, the first entry wins and the others are skipped with a warning.
+    /// - Items of the default menu bar that use an accelerator of the table, like Copy or Close Window, are left out of
+    ///   the menu, so the table wins over them.
+    /// - Menus set with [`Config::with_menu`] are used as they are. Don't reuse their accelerators, the OS doesn't
+    ///   guarantee which binding fires.
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    pub fn with_accelerators(
+        mut self,
+        accelerators: Vec<(muda::accelerator::Accelerator, ActionId)>,
+    ) -> Self {
+        self.accelerators = accelerators;
+        self
+    }
+
     /// Sets the menu the window will use. This will override the default menu bar.
     ///
     /// > Note: Menu will be hidden if
@@ -0,0 +1,150 @@
+use serde::Deserialize;
+
+/// What happens when the user right-clicks the page, set with
+/// [`Config::with_context_menu`](crate::Config::with_context_menu).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ContextMenuMode {
+    /// No context menu is shown
+    Disabled,
+    /// The webview's own context menu is shown, with entries like Reload and Inspect
+    Default,
+    /// A native menu with the given items is shown. Clicks are delivered through
+    /// [`use_context_menu_handler`](crate::use_context_menu_handler).
+    Custom(Vec<ContextMenuItem>),
+}
+
+/// An entry of a custom context menu
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ContextMenuItem {
+    /// A clickable entry
+    Item {
+        /// The id passed to the context menu handler when this entry is clicked
+        id: String,
+        /// The text of the entry
+        label: String,
+    },
+    /// A line between entries
+    Separator,
+}
+
+impl ContextMenuItem {
+    /// Create a clickable entry
+    pub fn item(id: impl Into<String>, label: impl Into<String>) -> Self {
+        ContextMenuItem::Item {
+            id: id.into(),
+            label: label.into(),
+        }
+    }
+}
+
+/// The element the user right-clicked to open the context menu
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[non_exhaustive]
+pub struct ContextMenuTarget {
+    /// The lowercase tag name of the element, like `div`
+    pub tag_name: String,
+    /// The `id` attribute of the element or its closest ancestor that has one
+    pub id: Option<String>,
+}
+
+/// An entry of a custom context menu was clicked
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ContextMenuEvent {
+    /// The id of the entry that was clicked
+    pub item_id: String,
+    /// The element that was right-clicked
+    pub target: ContextMenuTarget,
+}
+
+/// The contextmenu event carried over IPC when a custom menu should open
+#[derive(Deserialize)]
+pub(crate) struct ContextMenuRequest {
+    pub x: f64,
+    pub y: f64,
+    #[serde(flatten)]
+    pub target: ContextMenuTarget,
+}
+
+/// Prevents the webview's own menu and asks us to show the custom one instead.
+///
+/// The webviews don't have a reliable native switch for their context menu, so all of the modes except `Default` rely
+/// on cancelling the contextmenu event in the page.
+pub(crate) const CUSTOM_MENU_SCRIPT: &str = r#"
+document.addEventListener('contextmenu', function(e) {
+    e.preventDefault();
+    let target = e.target instanceof Element ? e.target : null;
+    let withId = target != null ? target.closest('[id]') : null;
+    window.ipc.postMessage(JSON.stringify({
+        method: 'context_menu',
+        params: {
+            x: e.clientX,
+            y: e.clientY,
+            tag_name: target != null ? target.tagName.toLowerCase() : '',
+            id: withId != null ? withId.id : null,
+        },
+    }));
+});
+"#;
+
+/// Menu ids are global, so ours are prefixed to keep them apart from the menu bar's
+#[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
+const MENU_ID_PREFIX: &str = "dioxus-context-menu:";
+
+/// Build the native menu for a custom context menu
+#[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
+pub(crate) fn build_menu(items: &[ContextMenuItem]) -> muda::Menu {
+    use muda::{MenuItem, PredefinedMenuItem};
+
+    let menu = muda::Menu::new();
+    for item in items {
+        let result = match item {
+            ContextMenuItem::Item { id, label } => menu.append(&MenuItem::with_id(
+                format!("{MENU_ID_PREFIX}{id}"),
+                label,
+                true,
+                None,
+            )),
+            ContextMenuItem::Separator => menu.append(&PredefinedMenuItem::separator()),
+        };
+
+        if let Err(err) = result {
+            tracing::error!("Failed to add {item:?} to the context menu: {err}");
+        }
+    }
+    menu
+}
+
+/// Get the id the user gave a context menu entry, if this menu event came from one
+#[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
+pub(crate) fn item_id(event: &muda::MenuEvent) -> Option<&str> {
+    event.id().0.strip_prefix(MENU_ID_PREFIX)
+}
+
+/// Show a context menu at a position relative to the top left corner of the window
+#[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
+pub(crate) fn show_menu(menu: &muda::Menu, window: &tao::window::Window, x: f64, y: f64) {
+    use muda::ContextMenu;
+
+    let position = Some(muda::Position::Logical(muda::LogicalPosition::new(x, y)));
+
+    #[cfg(target_os = "windows")]
+    {
+        use tao::platform::windows::WindowExtWindows;
+        menu.show_context_menu_for_hwnd(window.hwnd() as _, position);
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        use tao::platform::unix::WindowExtUnix;
+        menu.show_context_menu_for_gtk_window(window.gtk_window().as_ref(), position);
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        use tao::platform::macos::WindowExtMacOS;
+        menu.show_context_menu_for_nsview(window.ns_view() as _, position);
+    }
+}
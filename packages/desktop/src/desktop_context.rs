@@ -1,22 +1,34 @@
 use crate::{
     app::SharedContext,
     assets::AssetHandlerRegistry,
+    commands::CommandRegistry,
     file_upload::NativeFileHover,
-    ipc::UserWindowEvent,
+    ipc::{InvalidMessages, UserWindowEvent},
+    launch::LaunchError,
     query::QueryEngine,
     shortcut::{HotKey, ShortcutHandle, ShortcutRegistryError},
     webview::WebviewInstance,
-    AssetRequest, Config, WryEventHandler,
+    AssetRequest, CaptureError, Config, DialogButton, DialogOptions, MediaPreferences, PowerState,
+    PrintError, WryEventHandler,
 };
 use dioxus_core::{
-    prelude::{Callback, ScopeId},
-    VirtualDom,
+    prelude::{current_scope_id, spawn, Callback, ScopeId},
+    Runtime, VirtualDom,
+};
+use futures_util::FutureExt;
+use std::{
+    cell::{Cell, RefCell},
+    future::Future,
+    rc::{Rc, Weak},
 };
-use std::rc::{Rc, Weak};
 use tao::{
+    dpi::{PhysicalPosition, PhysicalSize, PixelUnit, Size},
     event::Event,
     event_loop::EventLoopWindowTarget,
-    window::{Fullscreen as WryFullscreen, Window, WindowId},
+    monitor::MonitorHandle,
+    window::{
+        Fullscreen as WryFullscreen, UserAttentionType, Window, WindowId, WindowSizeConstraints,
+    },
 };
 use wry::{RequestAsyncResponder, WebView};
 
@@ -64,8 +76,35 @@ pub struct DesktopService {
     /// The receiver for queries about the current window
     pub(super) query: QueryEngine,
     pub(crate) asset_handlers: AssetHandlerRegistry,
+    pub(crate) commands: CommandRegistry,
     pub(crate) file_hover: NativeFileHover,
 
+    /// Starts confirming a user's request to close this window. See [`DesktopService::set_close_handler`]. It returns
+    /// `false` if the scope that set it is gone, so the close should go through.
+    pub(crate) close_handler: RefCell<Option<Box<dyn FnMut() -> bool>>>,
+
+    /// Changes whenever the close handler is set or removed, so a handler that ran can tell if it was replaced
+    pub(crate) close_handler_generation: Cell<u64>,
+
+    zoom_level: Cell<f64>,
+
+    /// If spellchecking was turned on or off, which is applied again whenever a page loads
+    spellcheck: Cell<Option<bool>>,
+
+    /// The latest media preferences the page reported
+    pub(crate) media_preferences: Cell<MediaPreferences>,
+
+    /// The messages from the webview that couldn't be understood
+    pub(crate) invalid_messages: Rc<InvalidMessages>,
+
+    /// The latest `navigator.onLine` the page reported
+    pub(crate) online: Cell<bool>,
+
+    user_agent: Option<String>,
+
+    /// The size constraints of the window, which tao doesn't let us read back
+    pub(crate) size_constraints: Cell<WindowSizeConstraints>,
+
     #[cfg(target_os = "ios")]
     pub(crate) views: Rc<std::cell::RefCell<Vec<*mut objc::runtime::Object>>>,
 }
@@ -86,6 +125,8 @@ impl DesktopService {
         shared: Rc<SharedContext>,
         asset_handlers: AssetHandlerRegistry,
         file_hover: NativeFileHover,
+        invalid_messages: Rc<InvalidMessages>,
+        user_agent: Option<String>,
     ) -> Self {
         Self {
             window,
@@ -93,7 +134,17 @@ impl DesktopService {
             shared,
             asset_handlers,
             file_hover,
+            commands: Default::default(),
             query: Default::default(),
+            close_handler: Default::default(),
+            close_handler_generation: Default::default(),
+            zoom_level: Cell::new(1.0),
+            spellcheck: Default::default(),
+            media_preferences: Default::default(),
+            invalid_messages,
+            online: Cell::new(true),
+            user_agent,
+            size_constraints: Default::default(),
             #[cfg(target_os = "ios")]
             views: Default::default(),
         }
@@ -106,7 +157,15 @@ impl DesktopService {
     /// You can use this to control other windows from the current window.
     ///
     /// Be careful to not create a cycle of windows, or you might leak memory.
-    pub fn new_window(&self, dom: VirtualDom, cfg: Config) -> WeakDesktopContext {
+    ///
+    /// Returns an error instead of opening the window if the config can't be used, like
+    /// [`try_launch_virtual_dom`](crate::launch::try_launch_virtual_dom) does.
+    pub fn new_window(
+        &self,
+        dom: VirtualDom,
+        cfg: Config,
+    ) -> Result<WeakDesktopContext, LaunchError> {
+        cfg.validate()?;
         let window = WebviewInstance::new(cfg, dom, self.shared.clone());
 
         let cx = window.dom.in_runtime(|| {
@@ -122,7 +181,107 @@ impl DesktopService {
 
         self.shared.pending_webviews.borrow_mut().push(window);
 
-        Rc::downgrade(&cx)
+        Ok(Rc::downgrade(&cx))
+    }
+
+    /// Replace the root component of this window with a new virtual dom without closing the window.
+    ///
+    /// The current virtual dom is dropped, running any cleanup, and the new one is rebuilt from scratch once the
+    /// webview reloads. The window keeps its size, position, and other state.
+    pub fn replace_root(&self, dom: VirtualDom) {
+        self.shared
+            .pending_roots
+            .borrow_mut()
+            .push((self.id(), dom));
+
+        _ = self
+            .shared
+            .proxy
+            .send_event(UserWindowEvent::ReplaceRoot(self.id()));
+    }
+
+    /// Reload the page of this window.
+    ///
+    /// The interpreter is loaded again and the virtual dom is rebuilt into the fresh page, just like at launch. If the
+    /// page hasn't finished loading yet, the reload happens once it has.
+    pub fn reload(&self) {
+        _ = self
+            .shared
+            .proxy
+            .send_event(UserWindowEvent::Reload(self.id()));
+    }
+
+    /// Point this window at a url, replacing the app's page.
+    ///
+    /// The virtual dom keeps running, but its edits are only applied if the new page loads the Dioxus interpreter.
+    pub fn load_url(&self, url: &str) {
+        _ = self.shared.proxy.send_event(UserWindowEvent::LoadUrl {
+            id: self.id(),
+            url: url.to_string(),
+        });
+    }
+
+    /// Replace the app's page in this window with a string of html.
+    ///
+    /// Like [`DesktopService::load_url`], the virtual dom is only rendered again if the html loads the interpreter.
+    pub fn load_html(&self, html: &str) {
+        _ = self.shared.proxy.send_event(UserWindowEvent::LoadHtml {
+            id: self.id(),
+            html: html.to_string(),
+        });
+    }
+
+    /// Go back to the previous page in the webview's history, like the back button of a browser.
+    ///
+    /// Dioxus apps render into a single page, so this only does something after the webview navigated to another page,
+    /// for example with [`DesktopService::load_url`].
+    pub fn go_back(&self) {
+        self.run_navigation_script("window.history.back()");
+    }
+
+    /// Go forward to the next page in the webview's history, like the forward button of a browser.
+    ///
+    /// Like [`DesktopService::go_back`], this is a no-op for apps that never leave their own page.
+    pub fn go_forward(&self) {
+        self.run_navigation_script("window.history.forward()");
+    }
+
+    /// Stop loading the current page
+    pub fn stop_loading(&self) {
+        self.run_navigation_script("window.stop()");
+    }
+
+    /// Check if there is a page to go back to.
+    ///
+    /// The webviews on macOS and Linux don't say if they can go back, so there this only checks that the history has
+    /// more than one entry.
+    pub fn can_go_back(self: &Rc<Self>) -> impl Future<Output = bool> {
+        self.query
+            .new_expression_query::<bool>(
+                "window.navigation ? window.navigation.canGoBack : window.history.length > 1",
+                self.clone(),
+            )
+            .resolve()
+            .map(|result| result.unwrap_or(false))
+    }
+
+    /// Check if there is a page to go forward to.
+    ///
+    /// The webviews on macOS and Linux don't say if they can go forward, so this is always `false` there.
+    pub fn can_go_forward(self: &Rc<Self>) -> impl Future<Output = bool> {
+        self.query
+            .new_expression_query::<bool>(
+                "window.navigation ? window.navigation.canGoForward : false",
+                self.clone(),
+            )
+            .resolve()
+            .map(|result| result.unwrap_or(false))
+    }
+
+    fn run_navigation_script(&self, script: &str) {
+        if let Err(e) = self.webview.evaluate_script(script) {
+            tracing::warn!("Navigating the webview failed: {e}");
+        }
     }
 
     /// trigger the drag-window event
@@ -139,12 +298,217 @@ impl DesktopService {
         }
     }
 
+    /// Set the smallest size the user can resize the window to, or remove the limit with `None`
+    pub fn set_min_inner_size<S: Into<Size>>(&self, size: Option<S>) {
+        let mut constraints = self.size_constraints.get();
+        (constraints.min_width, constraints.min_height) = size_to_units(size.map(Into::into));
+        self.size_constraints.set(constraints);
+        self.window.set_inner_size_constraints(constraints);
+    }
+
+    /// Set the largest size the user can resize the window to, or remove the limit with `None`
+    ///
+    /// Some Linux window managers ignore the maximum size, and it doesn't stop the window from being maximized.
+    pub fn set_max_inner_size<S: Into<Size>>(&self, size: Option<S>) {
+        let mut constraints = self.size_constraints.get();
+        (constraints.max_width, constraints.max_height) = size_to_units(size.map(Into::into));
+        self.size_constraints.set(constraints);
+        self.window.set_inner_size_constraints(constraints);
+    }
+
+    /// Describe every monitor connected to the computer
+    ///
+    /// The window also derefs to [`Window::available_monitors`] if you need the raw tao handles.
+    pub fn monitors(&self) -> Vec<MonitorInfo> {
+        let primary = self.window.primary_monitor();
+        self.window
+            .available_monitors()
+            .map(|monitor| MonitorInfo::new(&monitor, primary.as_ref()))
+            .collect()
+    }
+
+    /// Describe the monitor the window is currently on, if it can be found
+    pub fn current_monitor_info(&self) -> Option<MonitorInfo> {
+        let primary = self.window.primary_monitor();
+        self.window
+            .current_monitor()
+            .map(|monitor| MonitorInfo::new(&monitor, primary.as_ref()))
+    }
+
+    /// Get the position of the top left corner of the window, including its decorations, on the desktop.
+    ///
+    /// Wayland doesn't tell apps where their windows are, so this is `None` there instead of the `(0, 0)` tao would
+    /// report. It's also `None` on Android. The size and scale of the window don't have this problem and are available
+    /// through [`Window::inner_size`], [`Window::outer_size`], and [`Window::scale_factor`].
+    ///
+    /// This takes the place of [`Window::outer_position`], which is still available through the `window` field.
+    pub fn outer_position(&self) -> Option<PhysicalPosition<i32>> {
+        #[cfg(target_os = "linux")]
+        {
+            use gtk::prelude::{ObjectExt, WidgetExt};
+            use tao::platform::unix::WindowExtUnix;
+
+            let display = self.window.gtk_window().display();
+            if display.type_().name() == "GdkWaylandDisplay" {
+                return None;
+            }
+        }
+
+        self.window.outer_position().ok()
+    }
+
+    /// Center the window on the monitor it is on
+    ///
+    /// Wayland doesn't let apps move their windows, so this does nothing there.
+    pub fn center_window(&self) {
+        let monitor = self
+            .window
+            .current_monitor()
+            .or_else(|| self.window.primary_monitor());
+        self.center_on(monitor);
+    }
+
+    /// Center the window on the monitor under the cursor, where the user is most likely looking
+    pub(crate) fn center_on_active_monitor(&self) {
+        let monitor = self
+            .window
+            .cursor_position()
+            .ok()
+            .and_then(|cursor| self.window.monitor_from_point(cursor.x, cursor.y))
+            .or_else(|| self.window.primary_monitor());
+        self.center_on(monitor);
+    }
+
+    fn center_on(&self, monitor: Option<MonitorHandle>) {
+        let Some(monitor) = monitor else {
+            tracing::warn!("Can't center the window, no monitor was found");
+            return;
+        };
+
+        let (origin, bounds) = (monitor.position(), monitor.size());
+        let size = self.window.outer_size();
+        let x = origin.x + (bounds.width.saturating_sub(size.width) / 2) as i32;
+        let y = origin.y + (bounds.height.saturating_sub(size.height) / 2) as i32;
+        self.window.set_outer_position(PhysicalPosition::new(x, y));
+    }
+
     /// Toggle whether the window is maximized or not
     pub fn toggle_maximized(&self) {
         self.window.set_maximized(!self.window.is_maximized())
     }
 
-    /// Close this window
+    /// Show the progress of a long task, like a download, on the dock icon or taskbar button, or hide it with `None`.
+    ///
+    /// The progress is clamped to `0.0..=1.0`. On macOS and Linux the progress belongs to the whole app rather than this
+    /// window. Linux only shows it in launchers that implement the Unity launcher API, which look the app up by a
+    /// desktop file named after the executable. This does nothing on mobile.
+    pub fn set_progress(&self, progress: Option<f64>) {
+        _ = self.shared.proxy.send_event(UserWindowEvent::SetProgress {
+            id: self.id(),
+            progress,
+        });
+    }
+
+    /// Show a short badge, like an unread count, on the dock icon of the app, or remove it with `None`.
+    ///
+    /// Linux launchers and iOS can only show numbers, so other badges are ignored there. This does nothing on Windows and
+    /// Android.
+    pub fn set_badge(&self, badge: Option<String>) {
+        _ = self.shared.proxy.send_event(UserWindowEvent::SetBadge {
+            id: self.id(),
+            badge,
+        });
+    }
+
+    /// Flash the taskbar button or bounce the dock icon to get the user to look at this window, for example when a
+    /// background task finishes. This does nothing if the window is already focused.
+    ///
+    /// This takes the place of [`Window::request_user_attention`], which is still available through the `window` field.
+    pub fn request_user_attention(&self, level: AttentionLevel) {
+        if self.window.is_focused() {
+            return;
+        }
+        self.window.request_user_attention(Some(level.into()));
+    }
+
+    /// Stop asking for the user's attention, if [`DesktopService::request_user_attention`] still is
+    pub fn cancel_attention(&self) {
+        self.window.request_user_attention(None);
+    }
+
+    /// The languages the user picked in the OS, most preferred first, as tags like `en-US`. This is empty if the OS
+    /// doesn't say, which is always the case on Android.
+    ///
+    /// Use it to pick the language of the app, or to build the header of [`Config::with_accept_language`].
+    pub fn preferred_languages(&self) -> Vec<String> {
+        crate::locale::preferred_languages()
+    }
+
+    /// Turn spellchecking in text fields on or off. See [`Config::with_spellcheck`] for how each platform handles it.
+    pub fn set_spellcheck(&self, enabled: bool) {
+        self.spellcheck.set(Some(enabled));
+        self.apply_spellcheck();
+    }
+
+    /// Apply the spellcheck setting to the webview and the current page
+    pub(crate) fn apply_spellcheck(&self) {
+        let Some(enabled) = self.spellcheck.get() else {
+            return;
+        };
+
+        #[cfg(target_os = "linux")]
+        {
+            use webkit2gtk::{WebContextExt, WebViewExt};
+            use wry::WebViewExtUnix;
+
+            if let Some(context) = self.webview.webview().context() {
+                context.set_spell_checking_enabled(enabled);
+            }
+        }
+
+        let script = format!("document.documentElement.spellcheck = {enabled};");
+        if let Err(err) = self.webview.evaluate_script(&script) {
+            tracing::warn!("Failed to set spellchecking on the page: {err}");
+        }
+    }
+
+    /// The accessibility and appearance settings of the OS, like reduced motion, as the page sees them.
+    ///
+    /// The page reports them before its first render, and [`use_media_preferences_handler`](crate::use_media_preferences_handler)
+    /// is called when they change.
+    pub fn media_preferences(&self) -> MediaPreferences {
+        self.media_preferences.get()
+    }
+
+    /// If the page of this window is online, from `navigator.onLine`.
+    ///
+    /// This only means the computer is connected to a network, not that the network reaches the internet or any
+    /// server the app needs: being offline is reliable, being online is not. Treat failed requests as the real signal
+    /// of a server being unreachable. [`use_online_status_handler`](crate::use_online_status_handler) is called when
+    /// this changes.
+    pub fn is_online(&self) -> bool {
+        self.online.get()
+    }
+
+    /// Where the computer gets its power from and how full its battery is, to throttle background work on battery.
+    ///
+    /// Computers without a battery are reported as plugged in, as are mobile platforms for now.
+    /// [`use_power_state_handler`](crate::use_power_state_handler) is called when this changes.
+    pub fn power_state(&self) -> PowerState {
+        crate::power::power_state()
+    }
+
+    /// The number of messages from the webview of this window that were dropped because they couldn't be understood.
+    ///
+    /// Each one is also logged at warn level. See [`Config::with_strict_ipc`] to log them as errors instead.
+    pub fn dropped_ipc_messages(&self) -> usize {
+        self.invalid_messages.count()
+    }
+
+    /// Close this window, as if the user clicked its close button.
+    ///
+    /// The close handler of the window still gets to keep it open, and the window's close behaviour decides whether the
+    /// app exits. Use [`DesktopService::register_exit_handler`] to clean up when the app exits.
     pub fn close(&self) {
         let _ = self
             .shared
@@ -152,7 +516,7 @@ impl DesktopService {
             .send_event(UserWindowEvent::CloseWindow(self.id()));
     }
 
-    /// Close a particular window, given its ID
+    /// Close a particular window, given its ID. This works like [`DesktopService::close`] on that window.
     pub fn close_window(&self, id: WindowId) {
         let _ = self
             .shared
@@ -160,6 +524,108 @@ impl DesktopService {
             .send_event(UserWindowEvent::CloseWindow(id));
     }
 
+    /// If the app is running or exiting. Long running tasks can check this to stop early, and
+    /// [`use_app_state_handler`](crate::use_app_state_handler) is called when the app starts exiting.
+    pub fn app_state(&self) -> crate::AppState {
+        self.shared.app_state.get()
+    }
+
+    /// Exit the app, however many windows are open, for example from a File > Quit menu item.
+    ///
+    /// Unlike [`DesktopService::close`], this doesn't ask the close handlers of the windows or follow their close
+    /// behaviour. The handlers registered with [`DesktopService::register_exit_handler`] still run before the windows
+    /// are dropped, and the window state is saved like on any other exit.
+    pub fn quit(&self) {
+        let _ = self.shared.proxy.send_event(UserWindowEvent::Shutdown);
+    }
+
+    /// Intercept requests from the user to close this window, for example to ask them to save their work first.
+    ///
+    /// When the user tries to close the window, `handler` runs in the scope that registered it and the window stays
+    /// open until the future it returns resolves. If it resolves to `true` the window closes as usual, otherwise it
+    /// stays open. This includes closing the window with [`DesktopService::close`], so the handler shouldn't call it.
+    ///
+    /// The handler stays set after the component that set it is dropped, and the window then closes without asking.
+    /// [`use_close_handler`](crate::use_close_handler) removes it with the component instead.
+    pub fn set_close_handler<F>(&self, handler: impl FnMut() -> F + 'static)
+    where
+        F: Future<Output = bool> + 'static,
+    {
+        self.insert_close_handler(handler);
+    }
+
+    /// Set the close handler, returning the generation it was set with
+    pub(crate) fn insert_close_handler<F>(&self, mut handler: impl FnMut() -> F + 'static) -> u64
+    where
+        F: Future<Output = bool> + 'static,
+    {
+        let generation = self.bump_close_handler_generation();
+        let Some((runtime, scope_id)) = Runtime::current().ok().zip(current_scope_id().ok()) else {
+            tracing::warn!("Close handlers can only be registered from inside a component");
+            return generation;
+        };
+
+        let proxy = self.shared.proxy.clone();
+        let id = self.id();
+
+        *self.close_handler.borrow_mut() = Some(Box::new(move || {
+            runtime.on_scope(scope_id, || {
+                // Every live scope can see the desktop context from the root, so this fails once the scope is gone
+                if scope_id.consume_context::<DesktopContext>().is_none() {
+                    return false;
+                }
+
+                let confirm = handler();
+                let proxy = proxy.clone();
+                spawn(async move {
+                    if confirm.await {
+                        _ = proxy.send_event(UserWindowEvent::CloseConfirmed(id));
+                    }
+                });
+                true
+            })
+        }));
+
+        generation
+    }
+
+    /// Stop intercepting requests to close this window.
+    pub fn remove_close_handler(&self) {
+        self.bump_close_handler_generation();
+        self.close_handler.borrow_mut().take();
+    }
+
+    /// Remove the close handler if it is still the one set with `generation`
+    pub(crate) fn remove_close_handler_if(&self, generation: u64) {
+        if self.close_handler_generation.get() == generation {
+            self.remove_close_handler();
+        }
+    }
+
+    fn bump_close_handler_generation(&self) -> u64 {
+        let generation = self.close_handler_generation.get() + 1;
+        self.close_handler_generation.set(generation);
+        generation
+    }
+
+    /// Save the size, position, and maximized/fullscreen state of this window so it can be restored later.
+    ///
+    /// This uses the same file as [`Config::with_remember_window_state`].
+    pub fn save_window_state(&self) {
+        if let Some(path) = crate::app::window_state_file() {
+            crate::app::save_window_state(self, &path);
+        }
+    }
+
+    /// Restore this window to the last state saved with [`Config::with_remember_window_state`] or
+    /// [`DesktopService::save_window_state`].
+    ///
+    /// Returns `false` if there was no saved state to restore.
+    pub fn restore_window_state(&self) -> bool {
+        crate::app::window_state_file()
+            .is_some_and(|path| crate::app::restore_window_state(self, &path, None, None))
+    }
+
     /// change window to fullscreen
     pub fn set_fullscreen(&self, fullscreen: bool) {
         if let Some(handle) = &self.window.current_monitor() {
@@ -169,29 +635,357 @@ impl DesktopService {
         }
     }
 
+    /// Change the window to exclusive fullscreen, using the highest resolution the current monitor supports.
+    ///
+    /// Exclusive fullscreen changes the display's video mode. If the platform doesn't report any video modes this
+    /// falls back to borderless fullscreen, like [`DesktopService::set_fullscreen`].
+    pub fn set_exclusive_fullscreen(&self, fullscreen: bool) {
+        if !fullscreen {
+            self.window.set_fullscreen(None);
+            return;
+        }
+
+        let Some(monitor) = self.window.current_monitor() else {
+            return;
+        };
+
+        let mode = monitor.video_modes().max_by_key(|mode| {
+            let size = mode.size();
+            (
+                size.width * size.height,
+                mode.refresh_rate(),
+                mode.bit_depth(),
+            )
+        });
+
+        self.window.set_fullscreen(Some(match mode {
+            Some(mode) => WryFullscreen::Exclusive(mode),
+            None => WryFullscreen::Borderless(Some(monitor)),
+        }));
+    }
+
+    /// Check if the window is fullscreen, in either borderless or exclusive mode
+    pub fn is_fullscreen(&self) -> bool {
+        self.window.fullscreen().is_some()
+    }
+
+    /// Change the window's taskbar and titlebar icon.
+    ///
+    /// `rgba` holds `width * height` pixels with 4 bytes each. An error is returned if the length doesn't match.
+    pub fn set_window_icon(
+        &self,
+        rgba: Vec<u8>,
+        width: u32,
+        height: u32,
+    ) -> Result<(), tao::window::BadIcon> {
+        let icon = tao::window::Icon::from_rgba(rgba, width, height)?;
+        self.window.set_window_icon(Some(icon));
+        Ok(())
+    }
+
+    /// Clear all of the webview's browsing data, including cookies, localStorage, IndexedDB, and the cache.
+    ///
+    /// This is useful to implement a logout button when the app stores its session in the webview.
+    pub fn clear_browsing_data(&self) {
+        if let Err(e) = self.webview.clear_all_browsing_data() {
+            tracing::warn!("Clearing browsing data failed: {e}");
+        }
+    }
+
     /// launch print modal
+    ///
+    /// This opens the native print dialog of the webview on Windows, macOS, and Linux. The mobile webviews don't have
+    /// one, so there this does nothing.
     pub fn print(&self) {
         if let Err(e) = self.webview.print() {
             tracing::warn!("Open print modal failed: {e}");
         }
     }
 
+    /// Save the current page as a PDF at the given path, without showing the print dialog.
+    ///
+    /// The future resolves once the file is written. This needs a recent WebView2 runtime on Windows and macOS 11 or
+    /// newer, and fails with [`PrintError::Unsupported`] on older systems and on mobile. The page is laid out with the
+    /// default print settings of the webview, so margins and page size differ between platforms.
+    pub fn print_to_pdf(
+        &self,
+        path: impl AsRef<std::path::Path>,
+    ) -> impl Future<Output = Result<(), PrintError>> {
+        crate::print::print_to_pdf(&self.webview, path.as_ref()).map(|result| {
+            result.unwrap_or_else(|_| {
+                Err(PrintError::Failed(
+                    "the webview dropped the print request".to_string(),
+                ))
+            })
+        })
+    }
+
+    /// Capture what the webview is showing as a PNG.
+    ///
+    /// This fails with [`CaptureError::NotVisible`] instead of returning a blank image if the window is minimized or
+    /// hidden. Parts of the window covered by other windows are still captured on Windows and macOS.
+    pub fn capture_screenshot(&self) -> impl Future<Output = Result<Vec<u8>, CaptureError>> {
+        let capture = if self.window.is_minimized() || !self.window.is_visible() {
+            None
+        } else {
+            Some(crate::screenshot::capture(&self.webview))
+        };
+
+        async move {
+            match capture {
+                Some(capture) => capture.await.unwrap_or_else(|_| {
+                    Err(CaptureError::Failed(
+                        "the webview dropped the screenshot request".to_string(),
+                    ))
+                }),
+                None => Err(CaptureError::NotVisible),
+            }
+        }
+    }
+
     /// Set the zoom level of the webview
+    ///
+    /// The level is clamped between 0.25 and 5.0. A level of 1.0 is the default size.
     pub fn set_zoom_level(&self, level: f64) {
-        if let Err(e) = self.webview.zoom(level) {
-            tracing::warn!("Set webview zoom failed: {e}");
+        let level = level.clamp(0.25, 5.0);
+        match self.webview.zoom(level) {
+            Ok(()) => self.zoom_level.set(level),
+            Err(e) => tracing::warn!("Set webview zoom failed: {e}"),
         }
     }
 
+    /// Get the zoom level last set with [`DesktopService::set_zoom_level`]
+    ///
+    /// Zooming with the webview's built-in hotkeys isn't reflected here.
+    pub fn get_zoom_level(&self) -> f64 {
+        self.zoom_level.get()
+    }
+
+    /// Get the user agent set with [`Config::with_user_agent`](crate::Config::with_user_agent)
+    ///
+    /// Returns `None` if the webview uses its default user agent.
+    pub fn user_agent(&self) -> Option<&str> {
+        self.user_agent.as_deref()
+    }
+
     /// opens DevTool window
     pub fn devtool(&self) {
-        #[cfg(debug_assertions)]
+        self.open_devtools();
+    }
+
+    /// Open the webview inspector for this window.
+    ///
+    /// Devtools are only available in debug builds or with the `devtools` feature. Otherwise this logs a warning.
+    pub fn open_devtools(&self) {
+        #[cfg(any(debug_assertions, feature = "devtools"))]
         self.webview.open_devtools();
 
-        #[cfg(not(debug_assertions))]
+        #[cfg(not(any(debug_assertions, feature = "devtools")))]
+        tracing::warn!("Devtools are disabled in release builds");
+    }
+
+    /// Close the webview inspector for this window.
+    pub fn close_devtools(&self) {
+        #[cfg(any(debug_assertions, feature = "devtools"))]
+        self.webview.close_devtools();
+
+        #[cfg(not(any(debug_assertions, feature = "devtools")))]
         tracing::warn!("Devtools are disabled in release builds");
     }
 
+    /// Check if the webview inspector is open for this window.
+    ///
+    /// This is always `false` when devtools are disabled.
+    pub fn is_devtools_open(&self) -> bool {
+        #[cfg(any(debug_assertions, feature = "devtools"))]
+        return self.webview.is_devtools_open();
+
+        #[cfg(not(any(debug_assertions, feature = "devtools")))]
+        return false;
+    }
+
+    /// Replace the contents of the system clipboard with the given text.
+    #[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
+    pub fn set_clipboard_text(&self, text: impl Into<String>) {
+        _ = self.shared.proxy.send_event(UserWindowEvent::Clipboard(
+            crate::ipc::ClipboardRequest::SetText(text.into()),
+        ));
+    }
+
+    /// Read the system clipboard as text.
+    ///
+    /// The returned future resolves to `None` if the clipboard is empty or holds something other than text.
+    #[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
+    pub fn get_clipboard_text(&self) -> impl std::future::Future<Output = Option<String>> {
+        use futures_util::StreamExt;
+
+        let (tx, mut rx) = futures_channel::mpsc::unbounded();
+        _ = self.shared.proxy.send_event(UserWindowEvent::Clipboard(
+            crate::ipc::ClipboardRequest::GetText(tx),
+        ));
+
+        async move { rx.next().await.flatten() }
+    }
+
+    /// Replace the contents of the system clipboard with an image, given as 8 bit RGBA pixels with straight alpha, row
+    /// by row from the top left.
+    ///
+    /// The pixels are converted to what each platform stores, so other apps paste the same colors.
+    #[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
+    pub fn set_clipboard_image(&self, rgba: impl Into<Vec<u8>>, width: usize, height: usize) {
+        let rgba = rgba.into();
+        if Some(rgba.len())
+            != width
+                .checked_mul(height)
+                .and_then(|pixels| pixels.checked_mul(4))
+        {
+            tracing::error!(
+                "Failed to write the image to the clipboard: {} bytes don't make a {width}x{height} RGBA image",
+                rgba.len()
+            );
+            return;
+        }
+
+        let image = arboard::ImageData {
+            width,
+            height,
+            bytes: rgba.into(),
+        };
+        _ = self.shared.proxy.send_event(UserWindowEvent::Clipboard(
+            crate::ipc::ClipboardRequest::SetImage(image),
+        ));
+    }
+
+    /// Read the system clipboard as an image.
+    ///
+    /// The returned future resolves to `None` if the clipboard is empty or holds something other than an image, like
+    /// text.
+    #[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
+    pub fn get_clipboard_image(
+        &self,
+    ) -> impl std::future::Future<Output = Option<crate::ImageData>> {
+        use futures_util::StreamExt;
+
+        let (tx, mut rx) = futures_channel::mpsc::unbounded();
+        _ = self.shared.proxy.send_event(UserWindowEvent::Clipboard(
+            crate::ipc::ClipboardRequest::GetImage(tx),
+        ));
+
+        async move { rx.next().await.flatten() }
+    }
+
+    /// Show a native OS notification.
+    ///
+    /// The returned future resolves to an error if the notification couldn't be shown, for example because the user
+    /// disabled notifications for the app.
+    #[cfg(any(
+        target_os = "windows",
+        target_os = "macos",
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    ))]
+    pub fn notify(
+        &self,
+        notification: crate::NotificationBuilder,
+    ) -> impl Future<Output = Result<crate::NotificationHandle, crate::NotificationError>> {
+        let (request, handle) = crate::notification::NotificationRequest::new(notification);
+        _ = self
+            .shared
+            .proxy
+            .send_event(UserWindowEvent::Notification(request));
+        handle
+    }
+
+    /// Ask the user to pick one or more files with the native file dialog.
+    ///
+    /// `filters` is a list of `(label, extensions)` pairs, for example `("Images", &["png", "jpg"])`. The returned
+    /// future resolves to `None` if the user cancelled the dialog.
+    #[cfg(any(
+        target_os = "windows",
+        target_os = "macos",
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    ))]
+    pub fn open_file_dialog(
+        &self,
+        filters: &[(&str, &[&str])],
+        multiple: bool,
+    ) -> impl std::future::Future<Output = Option<Vec<std::path::PathBuf>>> {
+        let mut dialog = rfd::AsyncFileDialog::new().set_parent(&self.window);
+        for (label, extensions) in filters {
+            dialog = dialog.add_filter(*label, extensions);
+        }
+
+        async move {
+            let files = if multiple {
+                dialog.pick_files().await?
+            } else {
+                vec![dialog.pick_file().await?]
+            };
+
+            Some(files.iter().map(|file| file.path().to_path_buf()).collect())
+        }
+    }
+
+    /// Ask the user where to save a file with the native file dialog.
+    ///
+    /// The returned future resolves to `None` if the user cancelled the dialog.
+    #[cfg(any(
+        target_os = "windows",
+        target_os = "macos",
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    ))]
+    pub fn save_file_dialog(
+        &self,
+        default_name: &str,
+    ) -> impl std::future::Future<Output = Option<std::path::PathBuf>> {
+        let dialog = rfd::AsyncFileDialog::new()
+            .set_parent(&self.window)
+            .set_file_name(default_name);
+
+        async move {
+            dialog
+                .save_file()
+                .await
+                .map(|file| file.path().to_path_buf())
+        }
+    }
+
+    /// Show a native message box over this window, like an "Are you sure?" prompt, and get the button the user answered
+    /// with.
+    ///
+    /// The dialog is modal to the window, but the returned future doesn't block the event loop, so the app keeps
+    /// rendering while it's open. On Linux the dialog is shown with `zenity`, and resolves to
+    /// [`DialogButton::Cancel`] if it isn't installed.
+    #[cfg(any(
+        target_os = "windows",
+        target_os = "macos",
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    ))]
+    pub fn message_dialog(
+        &self,
+        options: DialogOptions,
+    ) -> impl std::future::Future<Output = DialogButton> {
+        let buttons = options.buttons;
+        let dialog = options.into_rfd().set_parent(&self.window);
+
+        async move { DialogButton::from_rfd(dialog.show().await, buttons) }
+    }
+
     /// Create a wry event handler that listens for wry events.
     /// This event handler is scoped to the currently active window and will only receive events that are either global or related to the current window.
     ///
@@ -231,6 +1025,32 @@ impl DesktopService {
         self.shared.shortcut_manager.remove_all()
     }
 
+    /// Register a callback that runs right before the app exits.
+    ///
+    /// Exit handlers run once the app starts exiting, right after [`use_app_state_handler`](crate::use_app_state_handler)
+    /// and before the windows and their virtual doms are dropped. That includes the window that was closed last.
+    /// Handlers registered from a window that was closed earlier run outside of its scope, which is gone by then.
+    /// Tasks spawned on the tokio runtime can be given a grace period to finish after the handlers run with
+    /// [`Config::with_exit_grace_period`].
+    pub fn register_exit_handler(&self, handler: impl FnOnce() + 'static) {
+        // Run the handler in the scope that registered it so it can still read signals and context
+        let scope = Runtime::current().ok().zip(current_scope_id().ok());
+        self.shared
+            .exit_handlers
+            .borrow_mut()
+            .push(Box::new(move || match scope {
+                // Every live scope can see the desktop context from the root, so this fails once the scope is gone
+                Some((runtime, scope_id))
+                    if runtime.on_scope(scope_id, || {
+                        scope_id.consume_context::<DesktopContext>().is_some()
+                    }) =>
+                {
+                    runtime.on_scope(scope_id, handler)
+                }
+                _ => handler(),
+            }));
+    }
+
     /// Provide a callback to handle asset loading yourself.
     /// If the ScopeId isn't provided, defaults to a global handler.
     /// Note that the handler is namespaced by name, not ScopeId.
@@ -291,6 +1111,67 @@ impl DesktopService {
     }
 }
 
+/// A monitor connected to the computer, returned by [`DesktopService::monitors`]
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct MonitorInfo {
+    /// The name the OS gives the monitor
+    pub name: Option<String>,
+    /// The position of the top left corner of the monitor on the desktop
+    pub position: PhysicalPosition<i32>,
+    /// The resolution of the monitor
+    pub size: PhysicalSize<u32>,
+    /// The scale factor of the monitor, used to convert between logical and physical pixels
+    pub scale_factor: f64,
+    /// If this is the primary monitor
+    pub is_primary: bool,
+}
+
+impl MonitorInfo {
+    fn new(monitor: &MonitorHandle, primary: Option<&MonitorHandle>) -> Self {
+        Self {
+            name: monitor.name(),
+            position: monitor.position(),
+            size: monitor.size(),
+            scale_factor: monitor.scale_factor(),
+            is_primary: primary == Some(monitor),
+        }
+    }
+}
+
+/// How urgently [`DesktopService::request_user_attention`] asks for the user's attention
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttentionLevel {
+    /// Flash the taskbar button until the window is focused, or bounce the dock icon once
+    Informational,
+    /// Flash the taskbar button, or bounce the dock icon, until the window is focused
+    Critical,
+}
+
+impl From<AttentionLevel> for UserAttentionType {
+    fn from(level: AttentionLevel) -> Self {
+        match level {
+            AttentionLevel::Informational => UserAttentionType::Informational,
+            AttentionLevel::Critical => UserAttentionType::Critical,
+        }
+    }
+}
+
+/// Split a size into the width and height units tao's size constraints use
+fn size_to_units(size: Option<Size>) -> (Option<PixelUnit>, Option<PixelUnit>) {
+    match size {
+        Some(Size::Physical(size)) => (
+            Some(PixelUnit::Physical(size.width.into())),
+            Some(PixelUnit::Physical(size.height.into())),
+        ),
+        Some(Size::Logical(size)) => (
+            Some(PixelUnit::Logical(size.width.into())),
+            Some(PixelUnit::Logical(size.height.into())),
+        ),
+        None => (None, None),
+    }
+}
+
 #[cfg(target_os = "ios")]
 fn is_main_thread() -> bool {
     use objc::runtime::{Class, BOOL, NO};
@@ -0,0 +1,123 @@
+/// A native message box, shown with [`DesktopService::message_dialog`](crate::DesktopService::message_dialog).
+#[derive(Debug, Clone, Default)]
+pub struct DialogOptions {
+    pub(crate) title: String,
+    pub(crate) description: String,
+    pub(crate) level: DialogLevel,
+    pub(crate) buttons: DialogButtons,
+}
+
+impl DialogOptions {
+    /// Create a dialog that shows a message with an Ok button
+    pub fn new(description: impl Into<String>) -> Self {
+        Self {
+            description: description.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Set the title of the dialog
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// Set how serious the message is, which picks the icon of the dialog
+    pub fn with_level(mut self, level: DialogLevel) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// Set the buttons the user can answer with
+    pub fn with_buttons(mut self, buttons: DialogButtons) -> Self {
+        self.buttons = buttons;
+        self
+    }
+
+    #[cfg(any(
+        target_os = "windows",
+        target_os = "macos",
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    ))]
+    pub(crate) fn into_rfd(self) -> rfd::AsyncMessageDialog {
+        let level = match self.level {
+            DialogLevel::Info => rfd::MessageLevel::Info,
+            DialogLevel::Warning => rfd::MessageLevel::Warning,
+            DialogLevel::Error => rfd::MessageLevel::Error,
+        };
+        let buttons = match self.buttons {
+            DialogButtons::Ok => rfd::MessageButtons::Ok,
+            DialogButtons::OkCancel => rfd::MessageButtons::OkCancel,
+            DialogButtons::YesNo => rfd::MessageButtons::YesNo,
+        };
+
+        rfd::AsyncMessageDialog::new()
+            .set_title(self.title)
+            .set_description(self.description)
+            .set_level(level)
+            .set_buttons(buttons)
+    }
+}
+
+/// How serious the message of a [`DialogOptions`] is
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DialogLevel {
+    /// Something the user should know
+    #[default]
+    Info,
+    /// Something that may go wrong
+    Warning,
+    /// Something that went wrong
+    Error,
+}
+
+/// The buttons of a [`DialogOptions`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DialogButtons {
+    /// Just Ok
+    #[default]
+    Ok,
+    /// Ok and Cancel
+    OkCancel,
+    /// Yes and No
+    YesNo,
+}
+
+/// The button the user answered a message dialog with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DialogButton {
+    /// Ok was clicked, or a dialog with only Ok was dismissed
+    Ok,
+    /// Cancel was clicked, or the dialog was dismissed
+    Cancel,
+    /// Yes was clicked
+    Yes,
+    /// No was clicked, or a Yes/No dialog was dismissed
+    No,
+}
+
+impl DialogButton {
+    #[cfg(any(
+        target_os = "windows",
+        target_os = "macos",
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    ))]
+    pub(crate) fn from_rfd(result: rfd::MessageDialogResult, buttons: DialogButtons) -> Self {
+        match (result, buttons) {
+            (rfd::MessageDialogResult::Yes, _) => Self::Yes,
+            (rfd::MessageDialogResult::Ok, _) => Self::Ok,
+            // A Yes/No dialog has no Cancel button to report
+            (_, DialogButtons::YesNo) => Self::No,
+            (_, DialogButtons::Ok) => Self::Ok,
+            _ => Self::Cancel,
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use std::path::{Component, Path, PathBuf};
+
+/// A download the page started, passed to the handler set with
+/// [`Config::with_download_handler`](crate::Config::with_download_handler).
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct DownloadRequest {
+    /// The url the file is downloaded from
+    pub url: String,
+
+    /// The name the page or server suggested for the file.
+    ///
+    /// This is stripped of any directories, so it is safe to join onto a directory of your choice.
+    pub suggested_filename: String,
+}
+
+/// A download that finished, successfully or not. Listen for these with
+/// [`use_download_completed_handler`](crate::use_download_completed_handler).
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct DownloadCompleted {
+    /// The url the file was downloaded from
+    pub url: String,
+
+    /// Where the file was saved. This is always `None` on macOS, and may be `None` if the download failed.
+    pub path: Option<PathBuf>,
+
+    /// If the whole file was downloaded
+    pub success: bool,
+}
+
+impl DownloadRequest {
+    pub(crate) fn new(url: String, suggested_path: &Path) -> Self {
+        // The suggested name comes from the page or the server, so we can't trust it to be a plain file name
+        let suggested = suggested_path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| {
+                let path = url.split(['?', '#']).next().unwrap_or_default();
+                path.rsplit('/').next().unwrap_or_default().to_string()
+            });
+
+        Self {
+            url,
+            suggested_filename: sanitize_filename(&suggested),
+        }
+    }
+}
+
+/// Turn an untrusted file name into one that can't escape the directory it is joined onto
+fn sanitize_filename(name: &str) -> String {
+    let name = name.rsplit(['/', '\\']).next().unwrap_or_default();
+    let name: String = name
+        .chars()
+        .filter(|c| !c.is_control() && !matches!(c, ':' | '*' | '?' | '"' | '<' | '>' | '|'))
+        .collect();
+    let name = name.trim().trim_start_matches('.');
+
+    if name.is_empty() {
+        return "download".to_string();
+    }
+
+    name.to_string()
+}
+
+/// Check the destination returned by the download handler before handing it to the webview
+pub(crate) fn check_destination(path: &Path) -> bool {
+    if !path.is_absolute() {
+        tracing::warn!(
+            "Cancelling download, the destination {} is not an absolute path",
+            path.display()
+        );
+        return false;
+    }
+
+    if path.components().any(|c| c == Component::ParentDir) {
+        tracing::warn!(
+            "Cancelling download, the destination {} contains `..`",
+            path.display()
+        );
+        return false;
+    }
+
+    true
+}
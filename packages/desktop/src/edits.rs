@@ -1,7 +1,7 @@
 use std::{
-    collections::VecDeque,
     sync::{Arc, RwLock},
     task::Waker,
+    time::{Duration, Instant},
 };
 
 use dioxus_interpreter_js::MutationState;
@@ -15,20 +15,33 @@ pub(crate) struct WryQueue {
 
 #[derive(Default)]
 pub(crate) struct WryQueueInner {
-    edit_queue: VecDeque<Vec<u8>>,
+    // If there are mutations written into the mutation state that haven't been sent to the webview yet.
+    // Instead of queuing up one buffer per render, we leave the mutations in the channel until the webview
+    // asks for them so multiple renders are coalesced into a single batch.
+    has_pending_edits: bool,
     edit_responder: Option<wry::RequestAsyncResponder>,
     // Stores any futures waiting for edits to be applied to the webview
     // NOTE: We don't use a Notify here because we need polling the notify to be cancel safe
     waiting_for_edits_flushed: Vec<Waker>,
     // If this webview is currently waiting for an edit to be flushed. We don't run the virtual dom while this is true to avoid running effects before the dom has been updated
     edits_in_progress: bool,
+    // When the last batch of edits was handed to the webview
+    edits_sent_at: Option<Instant>,
     mutation_state: MutationState,
 }
 
 impl WryQueue {
-    pub fn handle_request(&self, responder: wry::RequestAsyncResponder) {
+    /// Handle a request from the webview for more edits.
+    ///
+    /// The webview only asks once it has applied the last batch, so this returns how long that batch took to flush.
+    pub fn handle_request(&self, responder: wry::RequestAsyncResponder) -> Option<Duration> {
         let mut myself = self.inner.write().unwrap();
-        if let Some(bytes) = myself.edit_queue.pop_back() {
+        let flushed = myself.edits_sent_at.take().map(|sent| sent.elapsed());
+        if myself.has_pending_edits {
+            myself.has_pending_edits = false;
+            myself.edits_sent_at = Some(Instant::now());
+            let bytes = myself.mutation_state.export_memory();
+            tracing::trace!("Sent {} bytes of queued edits to the webview", bytes.len());
             responder.respond(wry::http::Response::new(bytes));
         } else {
             // There are now no edits that need to be applied to the webview
@@ -38,6 +51,7 @@ impl WryQueue {
             myself.edits_in_progress = false;
             myself.edit_responder = Some(responder);
         }
+        flushed
     }
 
     pub fn with_mutation_state_mut<O: 'static>(
@@ -51,13 +65,35 @@ impl WryQueue {
     /// Send a list of mutations to the webview
     pub(crate) fn send_edits(&self) {
         let mut myself = self.inner.write().unwrap();
-        let serialized_edits = myself.mutation_state.export_memory();
         // There are pending edits that need to be applied to the webview before we run futures
         myself.edits_in_progress = true;
         if let Some(responder) = myself.edit_responder.take() {
+            myself.edits_sent_at = Some(Instant::now());
+            let serialized_edits = myself.mutation_state.export_memory();
+            tracing::trace!(
+                "Sent {} bytes of edits to the webview",
+                serialized_edits.len()
+            );
             responder.respond(wry::http::Response::new(serialized_edits));
         } else {
-            myself.edit_queue.push_front(serialized_edits);
+            // The webview is still applying the last batch. Keep the mutations in the channel and send them
+            // all at once with the next request. Templates and the edits that use them stay in order since
+            // they are written to the same channel.
+            tracing::trace!("The webview is busy, queueing the edits for its next request");
+            myself.has_pending_edits = true;
+        }
+    }
+
+    /// Forget everything we know about the webview. Used when the page is reloaded with a new virtual dom.
+    pub(crate) fn reset(&self) {
+        let mut myself = self.inner.write().unwrap();
+        myself.has_pending_edits = false;
+        myself.edit_responder = None;
+        myself.edits_in_progress = false;
+        myself.edits_sent_at = None;
+        myself.mutation_state = MutationState::default();
+        for waker in myself.waiting_for_edits_flushed.drain(..) {
+            waker.wake();
         }
     }
 
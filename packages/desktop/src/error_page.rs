@@ -0,0 +1,146 @@
+use crate::config::ErrorPageHandler;
+use wry::WebView;
+
+/// A page the webview failed to load, passed to the handler of [`Config::with_error_page`](crate::Config::with_error_page).
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct NavigationError {
+    /// The url of the page that failed to load
+    pub url: String,
+    /// The error code of the webview. On Windows this is a `COREWEBVIEW2_WEB_ERROR_STATUS`, on Linux a
+    /// `WebKitNetworkError`, or 0 for errors of other domains.
+    pub code: i32,
+    /// What went wrong, in English
+    pub description: String,
+}
+
+/// Show the html `handler` returns instead of the webview's own error page when a page fails to load. This needs to be
+/// called on the main thread.
+pub(crate) fn install_error_page(webview: &WebView, handler: ErrorPageHandler) {
+    if let Err(err) = platform::install_error_page(webview, handler) {
+        tracing::error!("Failed to install the error page: {err}");
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use super::{ErrorPageHandler, NavigationError};
+    use webview2_com::{
+        take_pwstr,
+        Microsoft::Web::WebView2::Win32::{
+            COREWEBVIEW2_WEB_ERROR_STATUS, COREWEBVIEW2_WEB_ERROR_STATUS_OPERATION_CANCELED,
+        },
+        NavigationCompletedEventHandler,
+    };
+    use windows::{
+        core::{HSTRING, PWSTR},
+        Win32::{Foundation::BOOL, System::WinRT::EventRegistrationToken},
+    };
+    use wry::{WebView, WebViewExtWindows};
+
+    pub(super) fn install_error_page(
+        webview: &WebView,
+        handler: ErrorPageHandler,
+    ) -> Result<(), String> {
+        let core = unsafe { webview.controller().CoreWebView2() }
+            .map_err(|err| err.message().to_string())?;
+
+        let handler = NavigationCompletedEventHandler::create(Box::new(move |webview, args| {
+            let (Some(webview), Some(args)) = (webview, args) else {
+                return Ok(());
+            };
+
+            let mut success = BOOL::default();
+            unsafe { args.IsSuccess(&mut success)? };
+            if success.as_bool() {
+                return Ok(());
+            }
+
+            // Navigations replaced by another one or blocked by the navigation handler are canceled, not failed
+            let mut status = COREWEBVIEW2_WEB_ERROR_STATUS::default();
+            unsafe { args.WebErrorStatus(&mut status)? };
+            if status == COREWEBVIEW2_WEB_ERROR_STATUS_OPERATION_CANCELED {
+                return Ok(());
+            }
+
+            let mut url = PWSTR::null();
+            unsafe { webview.Source(&mut url)? };
+
+            let html = handler(NavigationError {
+                url: take_pwstr(url),
+                code: status.0,
+                description: describe(status).to_string(),
+            });
+            unsafe { webview.NavigateToString(&HSTRING::from(html)) }
+        }));
+
+        let mut token = EventRegistrationToken::default();
+        unsafe { core.add_NavigationCompleted(&handler, &mut token) }
+            .map_err(|err| err.message().to_string())
+    }
+
+    /// WebView2 only reports a status, so describe the ones a user can run into
+    fn describe(status: COREWEBVIEW2_WEB_ERROR_STATUS) -> &'static str {
+        match status.0 {
+            1..=5 => "The certificate of the server is invalid",
+            6 => "The server is unreachable",
+            7 => "The connection timed out",
+            8 => "The server sent an invalid response",
+            9 => "The connection was aborted",
+            10 => "The connection was reset",
+            11 => "The internet connection was lost",
+            12 => "Could not connect to the server",
+            13 => "The host name could not be resolved",
+            15 => "The redirect failed",
+            17 => "The server requires authentication",
+            18 => "The proxy requires authentication",
+            _ => "The page failed to load",
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use super::{ErrorPageHandler, NavigationError};
+    use webkit2gtk::{glib::error::ErrorDomain, NetworkError, PolicyError, WebViewExt};
+    use wry::{WebView, WebViewExtUnix};
+
+    pub(super) fn install_error_page(
+        webview: &WebView,
+        handler: ErrorPageHandler,
+    ) -> Result<(), String> {
+        webview
+            .webview()
+            .connect_load_failed(move |webview, _, url, error| {
+                // Navigations replaced by another one or blocked by the navigation handler are canceled, not failed
+                if error.matches(NetworkError::Cancelled) || error.is::<PolicyError>() {
+                    return false;
+                }
+
+                let html = handler(NavigationError {
+                    url: url.to_string(),
+                    code: error.kind::<NetworkError>().map_or(0, |kind| kind.code()),
+                    description: error.message().to_string(),
+                });
+                webview.load_alternate_html(&html, url, None);
+
+                // Skip the error page of WebKitGTK
+                true
+            });
+
+        Ok(())
+    }
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
+mod platform {
+    use super::ErrorPageHandler;
+    use wry::WebView;
+
+    pub(super) fn install_error_page(
+        _webview: &WebView,
+        _handler: ErrorPageHandler,
+    ) -> Result<(), String> {
+        Err("custom error pages are only supported on Windows and Linux".to_string())
+    }
+}
@@ -4,7 +4,34 @@ use crate::{
     element::DesktopElement,
     file_upload::{DesktopFileDragEvent, DesktopFileUploadForm},
 };
+use dioxus_core::{ElementId, Runtime};
 use dioxus_html::*;
+use std::{any::Any, panic::AssertUnwindSafe, rc::Rc};
+
+/// Run the handlers for an event, catching any panics that escape them.
+///
+/// Events are dispatched from inside the webview's callbacks, so a panic unwinding out of a handler would cross the
+/// FFI boundary and abort the whole app. Instead we log the panic with enough context to track down the handler and
+/// keep the app running.
+pub(crate) fn handle_event_catching_panics(
+    runtime: &Rc<Runtime>,
+    name: &str,
+    event: dioxus_core::Event<dyn Any>,
+    element: ElementId,
+) {
+    let result = std::panic::catch_unwind(AssertUnwindSafe(|| {
+        runtime.handle_event(name, event, element)
+    }));
+
+    if let Err(err) = result {
+        let message = err
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| err.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "<non-string panic payload>".to_string());
+        tracing::error!("Panic while handling `{name}` event on element {element:?}: {message}");
+    }
+}
 
 pub(crate) struct SerializedHtmlEventConverter;
 
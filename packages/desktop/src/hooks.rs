@@ -1,16 +1,28 @@
-use std::rc::Rc;
+use std::{cell::Cell, rc::Rc, time::Duration};
 
 use crate::{
-    assets::*, ipc::UserWindowEvent, shortcut::IntoAccelerator, window, DesktopContext,
-    ShortcutHandle, ShortcutRegistryError, WryEventHandler,
+    accelerators::ActionId,
+    assets::*,
+    commands::{CommandResponder, CommandResult},
+    context_menu::ContextMenuEvent,
+    download::DownloadCompleted,
+    ipc::UserWindowEvent,
+    shortcut::IntoAccelerator,
+    window, DesktopContext, MediaPreferences, PowerState, ShortcutHandle, ShortcutRegistryError,
+    WryEventHandler,
 };
 use dioxus_core::{
-    prelude::{consume_context, use_hook_with_cleanup},
+    prelude::{consume_context, spawn, use_hook_with_cleanup, Callback},
     use_hook, Runtime,
 };
 
 use dioxus_hooks::use_callback;
-use tao::{event::Event, event_loop::EventLoopWindowTarget};
+use tao::{
+    dpi::{LogicalSize, PhysicalPosition},
+    event::{Event, WindowEvent},
+    event_loop::EventLoopWindowTarget,
+    window::{Theme, WindowId},
+};
 use wry::RequestAsyncResponder;
 
 /// Get an imperative handle to the current window
@@ -38,6 +50,380 @@ pub fn use_wry_event_handler(
     )
 }
 
+/// A change to the size, position, or scale factor of the current window.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub enum WindowGeometryEvent {
+    /// The window was resized.
+    Resized {
+        /// The new inner size of the window
+        size: LogicalSize<f64>,
+        /// The scale factor of the monitor the window is on
+        scale_factor: f64,
+    },
+
+    /// The window was moved.
+    Moved {
+        /// The new outer position of the window
+        position: PhysicalPosition<i32>,
+    },
+
+    /// The window moved to a monitor with a different scale factor, or the monitor's scale factor changed.
+    ScaleFactorChanged {
+        /// The inner size the window will have at the new scale factor
+        size: LogicalSize<f64>,
+        /// The new scale factor
+        scale_factor: f64,
+    },
+}
+
+/// Register an event handler that runs when the current window is resized, moved, or changes scale factor.
+pub fn use_window_geometry_handler(
+    mut handler: impl FnMut(WindowGeometryEvent) + 'static,
+) -> WryEventHandler {
+    let desktop = use_window();
+
+    use_wry_event_handler(move |event, _| {
+        let Event::WindowEvent { event, .. } = event else {
+            return;
+        };
+
+        let event = match event {
+            WindowEvent::Resized(size) => {
+                let scale_factor = desktop.window.scale_factor();
+                WindowGeometryEvent::Resized {
+                    size: size.to_logical(scale_factor),
+                    scale_factor,
+                }
+            }
+            WindowEvent::Moved(position) => WindowGeometryEvent::Moved {
+                position: *position,
+            },
+            WindowEvent::ScaleFactorChanged {
+                scale_factor,
+                new_inner_size,
+            } => WindowGeometryEvent::ScaleFactorChanged {
+                size: new_inner_size.to_logical(*scale_factor),
+                scale_factor: *scale_factor,
+            },
+            _ => return,
+        };
+
+        handler(event)
+    })
+}
+
+/// Register an event handler that runs when the current window is resized, at most once per `interval`.
+///
+/// Dragging the edge of a window resizes it many times a second, and rendering a complex layout for each of them makes
+/// the resize stutter. This handler runs for the first resize right away, then with the latest size once per
+/// `interval` while the resizing goes on, and once more with the final size when it stops. The webview itself still
+/// follows the window on every resize, so only the work done in the handler is throttled.
+pub fn use_window_resize_handler(
+    interval: Duration,
+    handler: impl FnMut(LogicalSize<f64>) + 'static,
+) -> WryEventHandler {
+    let desktop = use_window();
+
+    // wrap the user's handler in something that keeps it up to date
+    let cb = use_callback(handler);
+    let throttle = use_hook(|| {
+        Rc::new(ResizeThrottle {
+            handler: cb,
+            pending: Cell::new(None),
+            waiting: Cell::new(false),
+            timer: Default::default(),
+        })
+    });
+
+    use_wry_event_handler(move |event, _| {
+        if let Event::WindowEvent {
+            event: WindowEvent::Resized(size),
+            ..
+        } = event
+        {
+            let size = size.to_logical(desktop.window.scale_factor());
+            throttle.resized(size, interval);
+        }
+    })
+}
+
+/// Asks the timer thread of a [`ResizeThrottle`] to wait for an interval and say when it passed
+type TimerRequest = (Duration, futures_channel::oneshot::Sender<()>);
+
+struct ResizeThrottle {
+    handler: Callback<LogicalSize<f64>>,
+    /// The latest size that arrived while waiting for the interval to pass
+    pending: Cell<Option<LogicalSize<f64>>>,
+    waiting: Cell<bool>,
+    /// The timer thread, started on the first resize. It stops once the throttle is dropped.
+    timer: std::cell::OnceCell<std::sync::mpsc::Sender<TimerRequest>>,
+}
+
+impl ResizeThrottle {
+    fn resized(self: &Rc<Self>, size: LogicalSize<f64>, interval: Duration) {
+        if self.waiting.get() {
+            self.pending.set(Some(size));
+            return;
+        }
+
+        self.handler.call(size);
+        self.waiting.set(true);
+
+        let throttle = self.clone();
+        spawn(async move {
+            loop {
+                if !throttle.wait(interval).await {
+                    break;
+                }
+
+                // Nothing arrived during the interval, so the resizing stopped and the last size was already handled
+                let Some(size) = throttle.pending.take() else {
+                    break;
+                };
+                throttle.handler.call(size);
+            }
+            throttle.waiting.set(false);
+        });
+    }
+
+    /// Wait for `interval` to pass. The desktop renderer doesn't always run on tokio, so one thread per throttle does
+    /// the waiting instead of a timer.
+    async fn wait(&self, interval: Duration) -> bool {
+        let timer = self.timer.get_or_init(|| {
+            let (tx, rx) = std::sync::mpsc::channel::<TimerRequest>();
+            std::thread::spawn(move || {
+                while let Ok((interval, done)) = rx.recv() {
+                    std::thread::sleep(interval);
+                    _ = done.send(());
+                }
+            });
+            tx
+        });
+
+        let (tx, rx) = futures_channel::oneshot::channel();
+        timer.send((interval, tx)).is_ok() && rx.await.is_ok()
+    }
+}
+
+/// Register an event handler that runs when the OS switches between light and dark mode.
+///
+/// Read the theme at launch with [`Window::theme`](tao::window::Window::theme) on the desktop context. Only Windows and
+/// macOS report theme changes while the app is running, so on other platforms the theme read at launch is all there is
+/// and this handler never runs.
+pub fn use_theme_change_handler(mut handler: impl FnMut(Theme) + 'static) -> WryEventHandler {
+    use_wry_event_handler(move |event, _| {
+        if let Event::WindowEvent {
+            event: WindowEvent::ThemeChanged(theme),
+            ..
+        } = event
+        {
+            handler(*theme);
+        }
+    })
+}
+
+/// A change in whether the current window has focus, passed to [`use_window_focus_handler`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum WindowFocusEvent {
+    /// The window gained focus
+    Gained,
+    /// The window lost focus
+    Lost {
+        /// If focus moved to another window of this app rather than to another app.
+        ///
+        /// This is best effort: it relies on the OS telling us about the newly focused window shortly after the old
+        /// one lost focus, which some Linux window managers don't do.
+        to_app_window: bool,
+    },
+}
+
+/// Register an event handler that runs when the current window gains or loses focus.
+///
+/// This is useful to pause work while the user is looking at something else.
+pub fn use_window_focus_handler(
+    mut handler: impl FnMut(WindowFocusEvent) + 'static,
+) -> WryEventHandler {
+    let window_id = use_window().id();
+
+    use_wry_event_handler(move |event, _| {
+        if let Event::UserEvent(UserWindowEvent::FocusChanged { id, event }) = event {
+            if *id == window_id {
+                handler(*event);
+            }
+        }
+    })
+}
+
+/// Register an event handler that runs when the media preferences of the current window change, for example when the
+/// user turns on reduced motion in their OS.
+///
+/// Read the current preferences with [`DesktopService::media_preferences`](crate::DesktopService::media_preferences).
+pub fn use_media_preferences_handler(
+    mut handler: impl FnMut(MediaPreferences) + 'static,
+) -> WryEventHandler {
+    let window_id = use_window().id();
+
+    use_wry_event_handler(move |event, _| {
+        if let Event::UserEvent(UserWindowEvent::MediaPreferencesChanged { id, preferences }) =
+            event
+        {
+            if *id == window_id {
+                handler(*preferences);
+            }
+        }
+    })
+}
+
+/// Register an event handler that runs when the page of the current window goes online or offline, with the new
+/// status.
+///
+/// See [`DesktopService::is_online`](crate::DesktopService::is_online) for what being online means.
+pub fn use_online_status_handler(mut handler: impl FnMut(bool) + 'static) -> WryEventHandler {
+    let window_id = use_window().id();
+
+    use_wry_event_handler(move |event, _| {
+        if let Event::UserEvent(UserWindowEvent::OnlineStatusChanged { id, online }) = event {
+            if *id == window_id {
+                handler(*online);
+            }
+        }
+    })
+}
+
+/// Register an event handler that runs when the computer is plugged in or out, or its battery level changes.
+///
+/// Read the current state with [`DesktopService::power_state`](crate::DesktopService::power_state). The state is
+/// checked every few seconds, so the handler runs a little after the change. Mobile platforms don't report changes yet.
+pub fn use_power_state_handler(mut handler: impl FnMut(PowerState) + 'static) -> WryEventHandler {
+    #[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
+    {
+        let desktop = use_window();
+        use_hook(|| crate::power::watch_power_state(desktop.shared.proxy.clone()));
+    }
+
+    use_wry_event_handler(move |event, _| {
+        if let Event::UserEvent(UserWindowEvent::PowerStateChanged(state)) = event {
+            handler(*state);
+        }
+    })
+}
+
+/// If the app is running or on its way out, read with [`DesktopService::app_state`](crate::DesktopService::app_state)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum AppState {
+    /// The event loop is running
+    Running,
+    /// The event loop was told to exit, and the windows and their virtual doms are about to be dropped
+    Exiting,
+}
+
+/// Register an event handler that runs when the app starts exiting, whether the last window was closed or the app quit.
+///
+/// The handler runs before the windows are dropped and before the exit handlers, so it's the place to cancel long
+/// running tasks and stop sending updates to the webview. Work spawned from it won't get a chance to run.
+pub fn use_app_state_handler(mut handler: impl FnMut(AppState) + 'static) -> WryEventHandler {
+    use_wry_event_handler(move |event, _| {
+        if let Event::UserEvent(UserWindowEvent::AppStateChanged(state)) = event {
+            handler(*state);
+        }
+    })
+}
+
+/// A window of the app was opened or closed, passed to [`use_window_lifecycle_handler`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum WindowLifecycleEvent {
+    /// The window was created and its webview is ready to render
+    Created,
+    /// The window was closed and its virtual dom dropped
+    Destroyed,
+}
+
+/// Register an event handler that runs when any window of the app is opened or closed.
+///
+/// This lets a component keep track of the open windows. Windows opened before the handler was registered, like the
+/// current one, aren't reported.
+pub fn use_window_lifecycle_handler(
+    mut handler: impl FnMut(WindowId, WindowLifecycleEvent) + 'static,
+) -> WryEventHandler {
+    use_wry_event_handler(move |event, _| {
+        if let Event::UserEvent(UserWindowEvent::WindowLifecycle { id, event }) = event {
+            handler(*id, *event);
+        }
+    })
+}
+
+/// Register an event handler that runs when one of the current window's accelerators is pressed.
+///
+/// The accelerators are set with [`Config::with_accelerators`](crate::Config::with_accelerators).
+pub fn use_accelerator_handler(mut handler: impl FnMut(&ActionId) + 'static) -> WryEventHandler {
+    let window_id = use_window().id();
+
+    use_wry_event_handler(move |event, _| {
+        if let Event::UserEvent(UserWindowEvent::Accelerator { id, action }) = event {
+            if *id == window_id {
+                handler(action);
+            }
+        }
+    })
+}
+
+/// Register an event handler that runs when an entry of the current window's custom context menu is clicked.
+///
+/// The menu is set with [`Config::with_context_menu`](crate::Config::with_context_menu).
+pub fn use_context_menu_handler(
+    mut handler: impl FnMut(&ContextMenuEvent) + 'static,
+) -> WryEventHandler {
+    let window_id = use_window().id();
+
+    use_wry_event_handler(move |event, _| {
+        if let Event::UserEvent(UserWindowEvent::ContextMenu { id, event }) = event {
+            if *id == window_id {
+                handler(event);
+            }
+        }
+    })
+}
+
+/// Register an event handler that runs when a download started by the current window finishes.
+///
+/// Downloads only start if a handler was set with [`Config::with_download_handler`](crate::Config::with_download_handler).
+pub fn use_download_completed_handler(
+    mut handler: impl FnMut(&DownloadCompleted) + 'static,
+) -> WryEventHandler {
+    let window_id = use_window().id();
+
+    use_wry_event_handler(move |event, _| {
+        if let Event::UserEvent(UserWindowEvent::DownloadCompleted { id, download }) = event {
+            if *id == window_id {
+                handler(download);
+            }
+        }
+    })
+}
+
+/// Register an event handler that runs when files are dragged over, dropped onto, or dragged out of the current
+/// window.
+///
+/// This uses the native drag-and-drop handler, so it won't fire if it was disabled with
+/// [`Config::with_disable_drag_drop_handler`](crate::Config::with_disable_drag_drop_handler).
+pub fn use_file_drop_handler(
+    mut handler: impl FnMut(&wry::DragDropEvent) + 'static,
+) -> WryEventHandler {
+    let window_id = use_window().id();
+
+    use_wry_event_handler(move |event, _| {
+        if let Event::UserEvent(UserWindowEvent::FileDrop { id, event }) = event {
+            if *id == window_id {
+                handler(event);
+            }
+        }
+    })
+}
+
 /// Register an event handler that runs when a muda event is processed.
 #[cfg_attr(
     docsrs,
@@ -113,6 +499,68 @@ pub fn use_asset_handler(
     );
 }
 
+/// Register a command that JavaScript in the current window can call with `window.ipc.invoke(name, args)`.
+///
+/// The handler gets the arguments of the call as JSON and runs in the scope of the component that registered it. The
+/// promise returned by `invoke` resolves with the value the handler returns, or rejects with its error. Calls of
+/// commands nothing is registered for are rejected. The command is removed when the component is dropped. Only the
+/// app's own page can call commands, not remote pages the window navigated to.
+///
+/// ```rust, ignore
+/// use_command("add", |args| async move {
+///     let a = args["a"].as_i64().ok_or("a is missing")?;
+///     let b = args["b"].as_i64().ok_or("b is missing")?;
+///     Ok(serde_json::json!(a + b))
+/// });
+/// // In JavaScript: await window.ipc.invoke("add", { a: 1, b: 2 })
+/// ```
+pub fn use_command<F>(name: &str, mut handler: impl FnMut(serde_json::Value) -> F + 'static)
+where
+    F: std::future::Future<Output = CommandResult> + 'static,
+{
+    // wrap the user's handler in something that keeps it up to date
+    let cb = use_callback(
+        move |(args, responder): (serde_json::Value, CommandResponder)| {
+            let result = handler(args);
+            spawn(async move { responder.respond(result.await) });
+        },
+    );
+
+    use_hook_with_cleanup(
+        || {
+            crate::window()
+                .commands
+                .register_handler(name.to_string(), cb);
+
+            Rc::new(name.to_string())
+        },
+        move |name| {
+            crate::window().commands.remove_handler(name.as_ref());
+        },
+    );
+}
+
+/// Intercept requests from the user to close the current window while this component is mounted. See
+/// [`DesktopService::set_close_handler`](crate::DesktopService::set_close_handler) for how the handler is used.
+///
+/// The handler is removed when the component is dropped, unless something else set a close handler since.
+pub fn use_close_handler<F>(mut handler: impl FnMut() -> F + 'static)
+where
+    F: std::future::Future<Output = bool> + 'static,
+{
+    // wrap the user's handler in something that keeps it up to date
+    let cb = use_callback(move |_| handler());
+
+    use_hook_with_cleanup(
+        move || {
+            let desktop = window();
+            let generation = desktop.insert_close_handler(move || cb(()));
+            (desktop, generation)
+        },
+        |(desktop, generation)| desktop.remove_close_handler_if(generation),
+    );
+}
+
 /// Get a closure that executes any JavaScript in the WebView context.
 pub fn use_global_shortcut(
     accelerator: impl IntoAccelerator,
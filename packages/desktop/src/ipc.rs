@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::cell::Cell;
 use tao::window::WindowId;
 
 #[non_exhaustive]
@@ -17,6 +18,22 @@ pub enum UserWindowEvent {
     #[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
     TrayMenuEvent(tray_icon::menu::MenuEvent),
 
+    /// Read or write the system clipboard on the main thread
+    #[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
+    Clipboard(ClipboardRequest),
+
+    /// Show a native notification on the main thread
+    #[cfg(any(
+        target_os = "windows",
+        target_os = "macos",
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    ))]
+    Notification(crate::notification::NotificationRequest),
+
     /// Poll the virtualdom
     Poll(WindowId),
 
@@ -30,6 +47,60 @@ pub enum UserWindowEvent {
     #[cfg(all(feature = "devtools", debug_assertions))]
     HotReloadEvent(dioxus_devtools::DevserverMsg),
 
+    /// Files were dragged over or dropped onto a window
+    FileDrop {
+        id: WindowId,
+        event: wry::DragDropEvent,
+    },
+
+    /// A download started by a window finished
+    DownloadCompleted {
+        id: WindowId,
+        download: crate::download::DownloadCompleted,
+    },
+
+    /// A window lost focus, and the window that took it over (if any) should have said so by now
+    FocusLost(WindowId),
+
+    /// An entry of a window's custom context menu was clicked
+    ContextMenu {
+        id: WindowId,
+        event: crate::context_menu::ContextMenuEvent,
+    },
+
+    /// An accelerator set with `Config::with_accelerators` was pressed in a window
+    Accelerator {
+        id: WindowId,
+        action: crate::accelerators::ActionId,
+    },
+
+    /// A window was opened or closed
+    WindowLifecycle {
+        id: WindowId,
+        event: crate::hooks::WindowLifecycleEvent,
+    },
+
+    /// The media preferences of a window's page changed
+    MediaPreferencesChanged {
+        id: WindowId,
+        preferences: crate::media::MediaPreferences,
+    },
+
+    /// The computer was plugged in or out, or its battery level changed
+    PowerStateChanged(crate::power::PowerState),
+
+    /// The page of a window went online or offline
+    OnlineStatusChanged {
+        id: WindowId,
+        online: bool,
+    },
+
+    /// A window gained or lost focus
+    FocusChanged {
+        id: WindowId,
+        event: crate::hooks::WindowFocusEvent,
+    },
+
     // Windows-only drag-n-drop fix events.
     WindowsDragDrop(WindowId),
     WindowsDragOver(WindowId, i32, i32),
@@ -38,13 +109,73 @@ pub enum UserWindowEvent {
     /// Create a new window
     NewWindow,
 
-    /// Close a given window (could be any window!)
+    /// Replace the root component of a window with a new virtual dom
+    ReplaceRoot(WindowId),
+
+    /// Reload the page of a window
+    Reload(WindowId),
+
+    /// Point a window at a url
+    LoadUrl {
+        id: WindowId,
+        url: String,
+    },
+
+    /// Point a window at a string of html
+    LoadHtml {
+        id: WindowId,
+        html: String,
+    },
+
+    /// Show the progress of a window's task on the dock or taskbar
+    SetProgress {
+        id: WindowId,
+        progress: Option<f64>,
+    },
+
+    /// Show a badge on the dock icon of a window's app
+    SetBadge {
+        id: WindowId,
+        badge: Option<String>,
+    },
+
+    /// Ask a given window to close (could be any window!), as if the user clicked its close button
     CloseWindow(WindowId),
 
+    /// The navigation policy of a window decided to open a url in a new window
+    OpenInNewWindow {
+        opener: WindowId,
+        url: String,
+    },
+
+    /// The app started exiting. This is passed to the wry event handlers directly, since the event loop stops before it
+    /// would deliver another event.
+    AppStateChanged(crate::hooks::AppState),
+
+    /// The close handler of a window agreed to let it close
+    CloseConfirmed(WindowId),
+
     /// Gracefully shutdown the entire app
     Shutdown,
 }
 
+/// A request to access the system clipboard, serviced by the event loop
+#[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
+#[derive(Debug, Clone)]
+pub enum ClipboardRequest {
+    /// Replace the clipboard contents with the given text
+    SetText(String),
+
+    /// Read the clipboard as text, sending `None` back if it doesn't hold any text
+    GetText(futures_channel::mpsc::UnboundedSender<Option<String>>),
+
+    /// Replace the clipboard contents with the given image
+    SetImage(arboard::ImageData<'static>),
+
+    /// Read the clipboard as an image, sending `None` back if it doesn't hold an image
+    GetImage(futures_channel::mpsc::UnboundedSender<Option<crate::clipboard::ImageData>>),
+}
+
 /// A message struct that manages the communication between the webview and the eventloop code
 ///
 /// This needs to be serializable across the JS boundary, so the method names and structs are sensitive.
@@ -62,6 +193,12 @@ pub enum IpcMethod<'a> {
     Query,
     BrowserOpen,
     Initialize,
+    DragWindow,
+    ToggleMaximize,
+    ContextMenu,
+    Invoke,
+    MediaPreferences,
+    OnlineStatus,
     Other(&'a str),
 }
 
@@ -73,11 +210,90 @@ impl IpcMessage {
             "query" => IpcMethod::Query,
             "browser_open" => IpcMethod::BrowserOpen,
             "initialize" => IpcMethod::Initialize,
+            "drag_window" => IpcMethod::DragWindow,
+            "toggle_maximize" => IpcMethod::ToggleMaximize,
+            "context_menu" => IpcMethod::ContextMenu,
+            "invoke" => IpcMethod::Invoke,
+            "media_preferences" => IpcMethod::MediaPreferences,
+            "online_status" => IpcMethod::OnlineStatus,
             _ => IpcMethod::Other(&self.method),
         }
     }
 
+    /// If the message only touches the window, so any page may send it. Everything else reaches the app, so it is
+    /// only taken from the app's own page and not from remote pages the window navigated to.
+    pub(crate) fn allowed_from_any_page(&self) -> bool {
+        matches!(
+            self.method(),
+            IpcMethod::DragWindow
+                | IpcMethod::ToggleMaximize
+                | IpcMethod::MediaPreferences
+                | IpcMethod::OnlineStatus
+        )
+    }
+
     pub(crate) fn params(self) -> serde_json::Value {
         self.params
     }
+
+    /// Deserialize the params of the message, dropping it with [`InvalidMessages::drop_message`] if they don't fit
+    pub(crate) fn parse_params<T: serde::de::DeserializeOwned>(
+        self,
+        invalid_messages: &InvalidMessages,
+    ) -> Option<T> {
+        match T::deserialize(&self.params) {
+            Ok(params) => Some(params),
+            Err(err) => {
+                let what = format!("{} message", self.method);
+                invalid_messages.drop_message(&what, err, &self.params.to_string());
+                None
+            }
+        }
+    }
+}
+
+/// The messages from the webview of a window that were dropped because they couldn't be understood
+pub(crate) struct InvalidMessages {
+    /// If they are logged as errors instead of warnings, set with `Config::with_strict_ipc`
+    strict: bool,
+    dropped: Cell<usize>,
+}
+
+impl InvalidMessages {
+    pub(crate) fn new(strict: bool) -> Self {
+        Self {
+            strict,
+            dropped: Cell::new(0),
+        }
+    }
+
+    /// Log and count a message from the webview we can't make sense of.
+    ///
+    /// A custom bridge or an interpreter that doesn't match this version of dioxus-desktop would otherwise fail
+    /// silently.
+    pub(crate) fn drop_message(&self, what: &str, err: impl std::fmt::Display, raw: &str) {
+        let raw = truncate_for_log(raw);
+        self.dropped.set(self.dropped.get() + 1);
+
+        if self.strict {
+            tracing::error!("Dropped an invalid {what} from the webview: {err}. Contents: {raw}");
+        } else {
+            tracing::warn!("Dropped an invalid {what} from the webview: {err}. Contents: {raw}");
+        }
+    }
+
+    /// The number of messages that were dropped so far
+    pub(crate) fn count(&self) -> usize {
+        self.dropped.get()
+    }
+}
+
+/// Cut text sent to or from the webview short enough to log, marking where it was cut
+pub(crate) fn truncate_for_log(text: &str) -> std::borrow::Cow<'_, str> {
+    const MAX_LOGGED_LEN: usize = 512;
+
+    match text.char_indices().nth(MAX_LOGGED_LEN) {
+        Some((end, _)) => format!("{}...", &text[..end]).into(),
+        None => text.into(),
+    }
 }
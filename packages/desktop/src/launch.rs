@@ -2,6 +2,7 @@ use crate::Config;
 use crate::{
     app::App,
     ipc::{IpcMethod, UserWindowEvent},
+    webview::Navigation,
 };
 use dioxus_core::*;
 use dioxus_document::eval;
@@ -13,6 +14,11 @@ use tao::event::{Event, StartCause, WindowEvent};
 /// This will block the main thread, and *must* be spawned on the main thread. This function does not assume any runtime
 /// and is equivalent to calling launch_with_props with the tokio feature disabled.
 pub fn launch_virtual_dom_blocking(virtual_dom: VirtualDom, mut desktop_config: Config) -> ! {
+    // Use `try_launch_virtual_dom` to get this error back instead
+    if let Err(err) = desktop_config.validate() {
+        panic!("{err}");
+    }
+
     let mut custom_event_handler = desktop_config.custom_event_handler.take();
     let (event_loop, mut app) = App::new(desktop_config, virtual_dom);
 
@@ -33,13 +39,31 @@ pub fn launch_virtual_dom_blocking(virtual_dom: VirtualDom, mut desktop_config:
                 WindowEvent::CloseRequested => app.handle_close_requested(window_id),
                 WindowEvent::Destroyed { .. } => app.window_destroyed(window_id),
                 WindowEvent::Resized(new_size) => app.resize_window(window_id, new_size),
+                WindowEvent::Focused(focused) => app.handle_focus_changed(window_id, focused),
                 _ => {}
             },
 
             Event::UserEvent(event) => match event {
                 UserWindowEvent::Poll(id) => app.poll_vdom(id),
                 UserWindowEvent::NewWindow => app.handle_new_window(),
-                UserWindowEvent::CloseWindow(id) => app.handle_close_msg(id),
+                UserWindowEvent::CloseWindow(id) => app.handle_close_requested(id),
+                UserWindowEvent::ReplaceRoot(id) => app.handle_replace_root(id),
+                UserWindowEvent::Reload(id) => app.handle_navigate(id, Navigation::Reload),
+                UserWindowEvent::LoadUrl { id, url } => {
+                    app.handle_navigate(id, Navigation::Url(url))
+                }
+                UserWindowEvent::LoadHtml { id, html } => {
+                    app.handle_navigate(id, Navigation::Html(html))
+                }
+                UserWindowEvent::SetProgress { id, progress } => {
+                    app.handle_set_progress(id, progress)
+                }
+                UserWindowEvent::SetBadge { id, badge } => app.handle_set_badge(id, badge),
+                UserWindowEvent::CloseConfirmed(id) => app.handle_close_confirmed(id),
+                UserWindowEvent::OpenInNewWindow { opener, url } => {
+                    app.handle_open_in_new_window(opener, url)
+                }
+                UserWindowEvent::FocusLost(id) => app.handle_focus_lost(id),
                 UserWindowEvent::Shutdown => app.control_flow = tao::event_loop::ControlFlow::Exit,
 
                 #[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
@@ -54,9 +78,37 @@ pub fn launch_virtual_dom_blocking(virtual_dom: VirtualDom, mut desktop_config:
                 #[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
                 UserWindowEvent::TrayIconEvent(evnt) => app.handle_tray_icon_event(evnt),
 
+                #[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
+                UserWindowEvent::Clipboard(request) => app.handle_clipboard_request(request),
+
+                #[cfg(any(
+                    target_os = "windows",
+                    target_os = "macos",
+                    target_os = "linux",
+                    target_os = "dragonfly",
+                    target_os = "freebsd",
+                    target_os = "netbsd",
+                    target_os = "openbsd"
+                ))]
+                UserWindowEvent::Notification(request) => request.show(),
+
                 #[cfg(all(feature = "devtools", debug_assertions))]
                 UserWindowEvent::HotReloadEvent(msg) => app.handle_hot_reload_msg(msg),
 
+                // Native file drops, finished downloads, focus changes, context menu clicks, accelerators, opened or
+                // closed windows, and media preference, power and online status changes are delivered to components
+                // through their wry event handlers
+                UserWindowEvent::FileDrop { .. } => {}
+                UserWindowEvent::DownloadCompleted { .. } => {}
+                UserWindowEvent::FocusChanged { .. } => {}
+                UserWindowEvent::ContextMenu { .. } => {}
+                UserWindowEvent::Accelerator { .. } => {}
+                UserWindowEvent::WindowLifecycle { .. } => {}
+                UserWindowEvent::MediaPreferencesChanged { .. } => {}
+                UserWindowEvent::PowerStateChanged(_) => {}
+                UserWindowEvent::OnlineStatusChanged { .. } => {}
+                UserWindowEvent::AppStateChanged(_) => {}
+
                 // Windows-only drag-n-drop fix events. We need to call the interpreter drag-n-drop code.
                 UserWindowEvent::WindowsDragDrop(id) => {
                     if let Some(webview) = app.webviews.get(&id) {
@@ -100,28 +152,170 @@ pub fn launch_virtual_dom_blocking(virtual_dom: VirtualDom, mut desktop_config:
                     IpcMethod::FileDialog => app.handle_file_dialog_msg(msg, id),
                     IpcMethod::UserEvent => {}
                     IpcMethod::Query => app.handle_query_msg(msg, id),
-                    IpcMethod::BrowserOpen => app.handle_browser_open(msg),
-                    IpcMethod::Other(_) => {}
+                    IpcMethod::BrowserOpen => app.handle_browser_open(msg, id),
+                    IpcMethod::DragWindow => app.handle_drag_window_msg(id),
+                    IpcMethod::ToggleMaximize => app.handle_toggle_maximize_msg(id),
+                    IpcMethod::ContextMenu => app.handle_context_menu_msg(msg, id),
+                    IpcMethod::Invoke => app.handle_invoke_msg(msg, id),
+                    IpcMethod::MediaPreferences => app.handle_media_preferences_msg(msg, id),
+                    IpcMethod::OnlineStatus => app.handle_online_status_msg(msg, id),
+                    IpcMethod::Other(method) => {
+                        if let Some(view) = app.webviews.get(&id) {
+                            let what = format!("{method} message");
+                            view.desktop_context.invalid_messages.drop_message(
+                                &what,
+                                "the method is unknown",
+                                &msg.params().to_string(),
+                            )
+                        }
+                    }
                 },
             },
             _ => {}
         }
 
+        if app.control_flow == tao::event_loop::ControlFlow::Exit {
+            app.handle_exiting();
+        }
         *control_flow = app.control_flow;
     })
 }
 
+/// An error that keeps the desktop app from starting.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum LaunchError {
+    /// The system webview the app renders into isn't installed or can't be loaded.
+    #[error("{platform_hint} ({reason})")]
+    WebViewUnavailable {
+        /// What the user needs to install, and where to get it
+        platform_hint: &'static str,
+        /// The error reported by the webview
+        reason: String,
+    },
+
+    /// The proxy set with [`Config::with_proxy`](crate::Config::with_proxy) can't be used on this platform.
+    #[error("The proxy can't be used: {reason}")]
+    ProxyUnsupported {
+        /// Why the webview can't use the proxy
+        reason: &'static str,
+    },
+
+    /// The window can't be made incognito with [`Config::with_incognito`](crate::Config::with_incognito).
+    #[error("The window can't be made incognito: {reason}")]
+    IncognitoUnsupported {
+        /// Why the window can't be incognito
+        reason: &'static str,
+    },
+
+    /// The index set with [`Config::with_custom_index`](crate::Config::with_custom_index) is missing a part Dioxus
+    /// needs.
+    #[error("The custom index can't be used: {reason}")]
+    InvalidIndex {
+        /// What the index is missing
+        reason: String,
+    },
+
+    /// The effect set with [`Config::with_background_effect`](crate::Config::with_background_effect) can't be drawn.
+    #[error("The background effect can't be used: {reason}")]
+    BackgroundEffectUnsupported {
+        /// Why the effect can't be drawn
+        reason: &'static str,
+    },
+
+    /// A header set with [`Config::with_headers`](crate::Config::with_headers) can't be sent.
+    #[error("The header {name:?} can't be sent because {reason}")]
+    InvalidHeader {
+        /// The name of the header
+        name: String,
+        /// Why the header can't be sent
+        reason: &'static str,
+    },
+}
+
+impl LaunchError {
+    pub(crate) fn webview_unavailable(reason: impl ToString) -> Self {
+        LaunchError::WebViewUnavailable {
+            platform_hint: webview_install_hint(),
+            reason: reason.to_string(),
+        }
+    }
+}
+
+fn webview_install_hint() -> &'static str {
+    if cfg!(target_os = "windows") {
+        "WebView2 Runtime not found; install it from https://developer.microsoft.com/microsoft-edge/webview2/"
+    } else if cfg!(target_os = "android") {
+        "Android System WebView not found; install or enable it from the Play Store"
+    } else if cfg!(any(target_os = "macos", target_os = "ios")) {
+        "WKWebView could not be loaded; update the operating system"
+    } else {
+        "webkit2gtk not found; install webkit2gtk 4.1 with your distribution's package manager"
+    }
+}
+
+/// Check that the system webview is installed, returning its version.
+///
+/// Call this before launching to show a friendly prompt instead of failing at launch. On Linux a missing webkit2gtk
+/// usually keeps the binary from starting at all, so this mostly matters on Windows where WebView2 is a separate
+/// install on older versions.
+pub fn check_webview() -> std::result::Result<String, LaunchError> {
+    wry::webview_version().map_err(LaunchError::webview_unavailable)
+}
+
+/// Like [`launch_virtual_dom`], but returns an error instead of panicking if the system webview is missing or the
+/// config can't be used on this platform.
+///
+/// The event loop never returns, so this only returns if the app couldn't launch.
+pub fn try_launch_virtual_dom(
+    virtual_dom: VirtualDom,
+    desktop_config: Config,
+) -> std::result::Result<std::convert::Infallible, LaunchError> {
+    check_webview()?;
+    desktop_config.validate()?;
+    launch_virtual_dom(virtual_dom, desktop_config)
+}
+
 /// Launches the WebView and runs the event loop, with configuration and root props.
 pub fn launch_virtual_dom(virtual_dom: VirtualDom, desktop_config: Config) -> ! {
     #[cfg(feature = "tokio_runtime")]
     {
-        tokio::runtime::Builder::new_multi_thread()
-            .enable_all()
-            .build()
-            .unwrap()
-            .block_on(tokio::task::unconstrained(async move {
-                launch_virtual_dom_blocking(virtual_dom, desktop_config)
-            }));
+        use crate::RuntimeFlavor;
+
+        match desktop_config.runtime_flavor.clone() {
+            RuntimeFlavor::MultiThread { worker_threads } => {
+                let mut builder = tokio::runtime::Builder::new_multi_thread();
+                if let Some(worker_threads) = worker_threads {
+                    builder.worker_threads(worker_threads);
+                }
+
+                builder
+                    .enable_all()
+                    .build()
+                    .unwrap()
+                    .block_on(tokio::task::unconstrained(async move {
+                        launch_virtual_dom_blocking(virtual_dom, desktop_config)
+                    }));
+            }
+            RuntimeFlavor::CurrentThread => {
+                let runtime = tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()
+                    .unwrap();
+
+                // The event loop never yields the main thread back to the runtime, so we drive the runtime on a
+                // single background thread and just enter it here so `tokio::spawn` works from components
+                let handle = runtime.handle().clone();
+                std::thread::spawn(move || runtime.block_on(std::future::pending::<()>()));
+
+                let _guard = handle.enter();
+                launch_virtual_dom_blocking(virtual_dom, desktop_config);
+            }
+            RuntimeFlavor::External(handle) => {
+                let _guard = handle.enter();
+                launch_virtual_dom_blocking(virtual_dom, desktop_config);
+            }
+        }
 
         unreachable!("The desktop launch function will never exit")
     }
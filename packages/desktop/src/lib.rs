@@ -4,23 +4,50 @@
 #![deny(missing_docs)]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
+mod accelerators;
 mod android_sync_lock;
 mod app;
 mod assets;
+mod certificates;
+#[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
+mod clipboard;
+mod commands;
 mod config;
+mod context_menu;
 mod desktop_context;
+mod dialog;
 mod document;
+mod download;
 mod edits;
 mod element;
+mod error_page;
 mod event_handlers;
 mod events;
 mod file_upload;
 mod hooks;
 mod ipc;
+mod locale;
+mod media;
 mod menubar;
+mod network;
+#[cfg(any(
+    target_os = "windows",
+    target_os = "macos",
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+mod notification;
+mod power;
+mod print;
 mod protocol;
 mod query;
+mod render_observer;
+mod screenshot;
 mod shortcut;
+mod taskbar;
 mod waker;
 mod webview;
 
@@ -35,7 +62,7 @@ pub mod launch;
 pub use tao;
 pub use tao::dpi::{LogicalPosition, LogicalSize};
 pub use tao::event::WindowEvent;
-pub use tao::window::WindowBuilder;
+pub use tao::window::{Theme, WindowBuilder};
 pub use wry;
 // Reexport muda only if we are on desktop platforms that support menus
 #[cfg(not(any(target_os = "ios", target_os = "android")))]
@@ -46,10 +73,42 @@ pub use muda;
 pub mod trayicon;
 
 // Public exports
+pub use accelerators::ActionId;
 pub use assets::AssetRequest;
-pub use config::{Config, WindowCloseBehaviour};
-pub use desktop_context::{window, DesktopContext, DesktopService, WeakDesktopContext};
+#[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
+pub use clipboard::ImageData;
+pub use commands::CommandResult;
+#[cfg(feature = "tokio_runtime")]
+pub use config::RuntimeFlavor;
+pub use config::{
+    AutoplayPolicy, BlurMode, Config, ExternalLinkPolicy, NavigationDecision, ProxyConfig,
+    WindowCloseBehaviour, WindowPosition, DEFAULT_CSP,
+};
+pub use context_menu::{ContextMenuEvent, ContextMenuItem, ContextMenuMode, ContextMenuTarget};
+pub use desktop_context::{
+    window, AttentionLevel, DesktopContext, DesktopService, MonitorInfo, WeakDesktopContext,
+};
+pub use dialog::{DialogButton, DialogButtons, DialogLevel, DialogOptions};
+pub use download::{DownloadCompleted, DownloadRequest};
+pub use error_page::NavigationError;
 pub use event_handlers::WryEventHandler;
 pub use hooks::*;
+pub use media::{ColorScheme, ContrastPreference, MediaPreferences};
+#[cfg(any(
+    target_os = "windows",
+    target_os = "macos",
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+pub use notification::{
+    NotificationBuilder, NotificationError, NotificationEvent, NotificationHandle,
+};
+pub use power::PowerState;
+pub use print::PrintError;
+pub use render_observer::RenderObserver;
+pub use screenshot::CaptureError;
 pub use shortcut::{ShortcutHandle, ShortcutRegistryError};
 pub use wry::RequestAsyncResponder;
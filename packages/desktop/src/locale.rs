@@ -0,0 +1,109 @@
+/// The languages the user prefers, most preferred first, as BCP 47 tags like `en-US`.
+///
+/// This is empty if the OS doesn't say, which is always the case on Android.
+pub(crate) fn preferred_languages() -> Vec<String> {
+    platform::preferred_languages()
+}
+
+/// Let the webview send the languages of an `Accept-Language` header with every request, not just with documents
+#[cfg(target_os = "linux")]
+pub(crate) fn apply_accept_language(webview: &wry::WebView, accept_language: &str) {
+    use webkit2gtk::{WebContextExt, WebViewExt};
+    use wry::WebViewExtUnix;
+
+    // WebKitGTK builds the header from a plain list of languages, so drop the weights
+    let languages: Vec<&str> = accept_language
+        .split(',')
+        .filter_map(|language| language.split(';').next())
+        .map(str::trim)
+        .filter(|language| !language.is_empty() && *language != "*")
+        .collect();
+
+    if let Some(context) = webview.webview().context() {
+        context.set_preferred_languages(&languages);
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use windows::core::PWSTR;
+    use windows::Win32::Globalization::{GetUserPreferredUILanguages, MUI_LANGUAGE_NAME};
+
+    pub(super) fn preferred_languages() -> Vec<String> {
+        let (mut count, mut len) = (0, 0);
+        let mut buffer = Vec::new();
+        let result = unsafe {
+            GetUserPreferredUILanguages(MUI_LANGUAGE_NAME, &mut count, PWSTR::null(), &mut len)
+        }
+        .and_then(|_| {
+            buffer.resize(len as usize, 0);
+            unsafe {
+                GetUserPreferredUILanguages(
+                    MUI_LANGUAGE_NAME,
+                    &mut count,
+                    PWSTR(buffer.as_mut_ptr()),
+                    &mut len,
+                )
+            }
+        });
+
+        if let Err(err) = result {
+            tracing::warn!("Failed to read the preferred languages: {err}");
+            return Vec::new();
+        }
+
+        // The languages are separated by nul characters, and the list ends with an empty one
+        buffer
+            .split(|c| *c == 0)
+            .filter(|language| !language.is_empty())
+            .map(String::from_utf16_lossy)
+            .collect()
+    }
+}
+
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+mod platform {
+    use objc::runtime::Object;
+    use objc::{class, msg_send, sel, sel_impl};
+    use std::ffi::{c_char, CStr};
+
+    pub(super) fn preferred_languages() -> Vec<String> {
+        #[allow(unexpected_cfgs)]
+        objc::rc::autoreleasepool(|| unsafe {
+            let languages: *mut Object = msg_send![class!(NSLocale), preferredLanguages];
+            let count: usize = msg_send![languages, count];
+            (0..count)
+                .filter_map(|i| {
+                    let language: *mut Object = msg_send![languages, objectAtIndex: i];
+                    let utf8: *const c_char = msg_send![language, UTF8String];
+                    (!utf8.is_null()).then(|| CStr::from_ptr(utf8).to_string_lossy().into_owned())
+                })
+                .collect()
+        })
+    }
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "ios")))]
+mod platform {
+    pub(super) fn preferred_languages() -> Vec<String> {
+        // `LANGUAGE` lists languages in order of preference, the others hold a single locale like `de_DE.UTF-8`
+        let Some(locales) = ["LANGUAGE", "LC_ALL", "LC_MESSAGES", "LANG"]
+            .into_iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|value| !value.is_empty())
+        else {
+            return Vec::new();
+        };
+
+        locales.split(':').filter_map(language_tag).collect()
+    }
+
+    fn language_tag(locale: &str) -> Option<String> {
+        // Drop the encoding and modifier, like `.UTF-8` and `@euro`
+        let locale = locale.split(['.', '@']).next()?;
+        if locale.is_empty() || locale == "C" || locale == "POSIX" {
+            return None;
+        }
+        Some(locale.replace('_', "-"))
+    }
+}
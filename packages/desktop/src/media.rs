@@ -0,0 +1,77 @@
+use serde::Deserialize;
+
+/// The accessibility and appearance settings the user picked in their OS, read from the webview's media queries.
+///
+/// Get them with [`DesktopService::media_preferences`](crate::DesktopService::media_preferences) and listen for
+/// changes with [`use_media_preferences_handler`](crate::use_media_preferences_handler).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[non_exhaustive]
+pub struct MediaPreferences {
+    /// If the user asked for less motion, from `prefers-reduced-motion`. Animations should be toned down or skipped.
+    pub reduced_motion: bool,
+    /// How much contrast the user asked for, from `prefers-contrast`
+    pub contrast: ContrastPreference,
+    /// If the user prefers a light or dark theme, from `prefers-color-scheme`
+    pub color_scheme: ColorScheme,
+}
+
+/// The contrast the user asked for, from the `prefers-contrast` media query
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum ContrastPreference {
+    /// The user didn't ask for a contrast
+    #[default]
+    NoPreference,
+    /// The user asked for more contrast
+    More,
+    /// The user asked for less contrast
+    Less,
+    /// The user picked their own colors, like Windows' contrast themes
+    Custom,
+}
+
+/// The theme the user prefers, from the `prefers-color-scheme` media query
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ColorScheme {
+    /// A light theme, which is also what the webview reports if the user has no preference
+    #[default]
+    Light,
+    /// A dark theme
+    Dark,
+}
+
+/// Reports the media preferences when the page starts loading and whenever they change.
+///
+/// Messages from the page are handled in order, so the first report arrives before the interpreter asks for the first
+/// render.
+pub(crate) const MEDIA_PREFERENCES_SCRIPT: &str = r#"
+(function() {
+    let queries = {
+        reducedMotion: matchMedia('(prefers-reduced-motion: reduce)'),
+        moreContrast: matchMedia('(prefers-contrast: more)'),
+        lessContrast: matchMedia('(prefers-contrast: less)'),
+        customContrast: matchMedia('(prefers-contrast: custom)'),
+        dark: matchMedia('(prefers-color-scheme: dark)'),
+    };
+    function report() {
+        let contrast = queries.moreContrast.matches ? 'more'
+            : queries.lessContrast.matches ? 'less'
+            : queries.customContrast.matches ? 'custom'
+            : 'no_preference';
+        window.ipc.postMessage(JSON.stringify({
+            method: 'media_preferences',
+            params: {
+                reduced_motion: queries.reducedMotion.matches,
+                contrast: contrast,
+                color_scheme: queries.dark.matches ? 'dark' : 'light',
+            },
+        }));
+    }
+    for (let query of Object.values(queries)) {
+        query.addEventListener('change', report);
+    }
+    report();
+})();
+"#;
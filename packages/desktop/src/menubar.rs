@@ -27,10 +27,19 @@ pub fn default_menu_bar() -> DioxusMenu {
     }
 }
 
+/// Creates the default menu bar without the items whose accelerators are reserved for something else
+#[cfg(not(any(target_os = "ios", target_os = "android")))]
+pub(crate) fn default_menu_bar_without(reserved: &[muda::accelerator::Accelerator]) -> DioxusMenu {
+    desktop_platforms::default_menu_bar_without(reserved)
+}
+
 #[cfg(not(any(target_os = "ios", target_os = "android")))]
 mod desktop_platforms {
     use super::*;
-    use muda::{Menu, MenuItem, PredefinedMenuItem, Submenu};
+    use muda::{
+        accelerator::{Accelerator, Code, Modifiers, CMD_OR_CTRL},
+        IsMenuItem, Menu, MenuItem, PredefinedMenuItem, Submenu,
+    };
 
     #[allow(unused)]
     pub fn init_menu_bar(menu: &Menu, window: &Window) {
@@ -54,39 +63,97 @@ mod desktop_platforms {
         }
     }
 
+    /// The conventional shortcut for the inspector: Cmd+Option+I on macOS and F12 everywhere else
+    fn devtools_accelerator() -> Accelerator {
+        if cfg!(target_os = "macos") {
+            Accelerator::new(Some(Modifiers::SUPER | Modifiers::ALT), Code::KeyI)
+        } else {
+            Accelerator::new(None, Code::F12)
+        }
+    }
+
+    fn as_items(items: &[PredefinedMenuItem]) -> Vec<&dyn IsMenuItem> {
+        items.iter().map(|item| item as &dyn IsMenuItem).collect()
+    }
+
     pub fn default_menu_bar() -> Menu {
+        default_menu_bar_without(&[])
+    }
+
+    /// Build the default menu bar, leaving out the items that use one of the reserved accelerators
+    pub fn default_menu_bar_without(reserved: &[Accelerator]) -> Menu {
+        // The accelerators muda gives the predefined items on each platform
+        let shortcut = |mods: Modifiers, key: Code| Some(Accelerator::new(Some(mods), key));
+        let command = |key: Code| shortcut(CMD_OR_CTRL, key);
+        let keep = |items: Vec<(PredefinedMenuItem, Option<Accelerator>)>| {
+            items
+                .into_iter()
+                .filter(|(_, accelerator)| !accelerator.is_some_and(|a| reserved.contains(&a)))
+                .map(|(item, _)| item)
+                .collect::<Vec<_>>()
+        };
+
         let menu = Menu::new();
         // since it is uncommon on windows to have an "application menu"
         // we add a "window" menu to be more consistent across platforms with the standard menu
         let window_menu = Submenu::new("Window", true);
-        window_menu
-            .append_items(&[
-                &PredefinedMenuItem::fullscreen(None),
-                &PredefinedMenuItem::separator(),
-                &PredefinedMenuItem::hide(None),
-                &PredefinedMenuItem::hide_others(None),
-                &PredefinedMenuItem::show_all(None),
-                &PredefinedMenuItem::maximize(None),
-                &PredefinedMenuItem::minimize(None),
-                &PredefinedMenuItem::close_window(None),
-                &PredefinedMenuItem::separator(),
-                &PredefinedMenuItem::quit(None),
-            ])
-            .unwrap();
+        let window_items = keep(vec![
+            (
+                PredefinedMenuItem::fullscreen(None),
+                if cfg!(target_os = "macos") {
+                    shortcut(Modifiers::SUPER | Modifiers::CONTROL, Code::KeyF)
+                } else {
+                    None
+                },
+            ),
+            (PredefinedMenuItem::separator(), None),
+            (PredefinedMenuItem::hide(None), command(Code::KeyH)),
+            (
+                PredefinedMenuItem::hide_others(None),
+                shortcut(CMD_OR_CTRL | Modifiers::ALT, Code::KeyH),
+            ),
+            (PredefinedMenuItem::show_all(None), None),
+            (PredefinedMenuItem::maximize(None), None),
+            (PredefinedMenuItem::minimize(None), command(Code::KeyM)),
+            (
+                PredefinedMenuItem::close_window(None),
+                if cfg!(target_os = "macos") {
+                    command(Code::KeyW)
+                } else {
+                    shortcut(Modifiers::ALT, Code::F4)
+                },
+            ),
+            (PredefinedMenuItem::separator(), None),
+            (
+                PredefinedMenuItem::quit(None),
+                if cfg!(target_os = "macos") {
+                    command(Code::KeyQ)
+                } else {
+                    None
+                },
+            ),
+        ]);
+        window_menu.append_items(&as_items(&window_items)).unwrap();
 
         let edit_menu = Submenu::new("Edit", true);
-        edit_menu
-            .append_items(&[
-                &PredefinedMenuItem::undo(None),
-                &PredefinedMenuItem::redo(None),
-                &PredefinedMenuItem::separator(),
-                &PredefinedMenuItem::cut(None),
-                &PredefinedMenuItem::copy(None),
-                &PredefinedMenuItem::paste(None),
-                &PredefinedMenuItem::separator(),
-                &PredefinedMenuItem::select_all(None),
-            ])
-            .unwrap();
+        let edit_items = keep(vec![
+            (PredefinedMenuItem::undo(None), command(Code::KeyZ)),
+            (
+                PredefinedMenuItem::redo(None),
+                if cfg!(target_os = "macos") {
+                    shortcut(CMD_OR_CTRL | Modifiers::SHIFT, Code::KeyZ)
+                } else {
+                    command(Code::KeyY)
+                },
+            ),
+            (PredefinedMenuItem::separator(), None),
+            (PredefinedMenuItem::cut(None), command(Code::KeyX)),
+            (PredefinedMenuItem::copy(None), command(Code::KeyC)),
+            (PredefinedMenuItem::paste(None), command(Code::KeyV)),
+            (PredefinedMenuItem::separator(), None),
+            (PredefinedMenuItem::select_all(None), command(Code::KeyA)),
+        ]);
+        edit_menu.append_items(&as_items(&edit_items)).unwrap();
 
         menu.append_items(&[&window_menu, &edit_menu]).unwrap();
 
@@ -98,7 +165,7 @@ mod desktop_platforms {
                     "dioxus-toggle-dev-tools",
                     "Toggle Developer Tools",
                     true,
-                    None,
+                    Some(devtools_accelerator()).filter(|a| !reserved.contains(a)),
                 )])
                 .unwrap();
 
@@ -0,0 +1,22 @@
+/// Reports `navigator.onLine` when the page starts loading and whenever the `online` and `offline` events fire.
+///
+/// Like the media preferences, the first report arrives before the interpreter asks for the first render.
+pub(crate) const ONLINE_STATUS_SCRIPT: &str = r#"
+(function() {
+    function report() {
+        window.ipc.postMessage(JSON.stringify({
+            method: 'online_status',
+            params: { online: navigator.onLine },
+        }));
+    }
+    window.addEventListener('online', report);
+    window.addEventListener('offline', report);
+    report();
+})();
+"#;
+
+/// The params of an `online_status` message
+#[derive(serde::Deserialize)]
+pub(crate) struct OnlineStatus {
+    pub(crate) online: bool,
+}
@@ -0,0 +1,140 @@
+use futures_channel::mpsc::{UnboundedReceiver, UnboundedSender};
+use futures_util::StreamExt;
+
+/// A native OS notification that can be shown with [`DesktopService::notify`](crate::DesktopService::notify).
+#[derive(Debug, Clone, Default)]
+pub struct NotificationBuilder {
+    pub(crate) title: String,
+    pub(crate) body: String,
+    pub(crate) icon: Option<String>,
+}
+
+impl NotificationBuilder {
+    /// Create a new notification with the given title
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Set the body text of the notification
+    pub fn body(mut self, body: impl Into<String>) -> Self {
+        self.body = body.into();
+        self
+    }
+
+    /// Set the icon of the notification. This is either a path to an image or the name of an icon from the icon theme.
+    pub fn icon(mut self, icon: impl Into<String>) -> Self {
+        self.icon = Some(icon.into());
+        self
+    }
+}
+
+/// Something the user did with a notification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum NotificationEvent {
+    /// The user clicked the notification
+    Clicked,
+    /// The notification was dismissed or expired
+    Closed,
+}
+
+/// An error showing a notification, usually because notifications are disabled or unsupported.
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("Failed to show notification: {0}")]
+pub struct NotificationError(String);
+
+/// A notification that is being displayed.
+pub struct NotificationHandle {
+    events: UnboundedReceiver<NotificationEvent>,
+}
+
+impl NotificationHandle {
+    /// Wait for the next thing the user does with the notification.
+    ///
+    /// This resolves to `None` once the notification is gone. Clicks and close events are only reported by the
+    /// notification services on Linux and the BSDs. On other platforms this resolves to `None` immediately.
+    pub async fn next_event(&mut self) -> Option<NotificationEvent> {
+        self.events.next().await
+    }
+}
+
+/// A request to show a notification, serviced by the event loop
+#[derive(Debug, Clone)]
+pub struct NotificationRequest {
+    pub(crate) notification: NotificationBuilder,
+    pub(crate) shown:
+        UnboundedSender<Result<UnboundedReceiver<NotificationEvent>, NotificationError>>,
+}
+
+impl NotificationRequest {
+    pub(crate) fn new(
+        notification: NotificationBuilder,
+    ) -> (
+        Self,
+        impl std::future::Future<Output = Result<NotificationHandle, NotificationError>>,
+    ) {
+        let (shown, mut rx) = futures_channel::mpsc::unbounded();
+
+        let handle = async move {
+            match rx.next().await {
+                Some(Ok(events)) => Ok(NotificationHandle { events }),
+                Some(Err(err)) => Err(err),
+                None => Err(NotificationError(
+                    "the event loop shut down before the notification was shown".to_string(),
+                )),
+            }
+        };
+
+        (
+            Self {
+                notification,
+                shown,
+            },
+            handle,
+        )
+    }
+
+    /// Show the notification. This needs to be called on the main thread on some platforms.
+    pub(crate) fn show(self) {
+        let mut notification = notify_rust::Notification::new();
+        notification
+            .summary(&self.notification.title)
+            .body(&self.notification.body);
+
+        if let Some(icon) = &self.notification.icon {
+            notification.icon(icon);
+        }
+
+        #[allow(unused_variables)]
+        let (events, rx) = futures_channel::mpsc::unbounded();
+
+        match notification.show() {
+            #[cfg(all(unix, not(target_os = "macos")))]
+            Ok(handle) => {
+                // The notification service only tells us about clicks if we block on it, so wait on another thread
+                std::thread::spawn(move || {
+                    handle.wait_for_action(|action| {
+                        let event = match action {
+                            "__closed" => NotificationEvent::Closed,
+                            _ => NotificationEvent::Clicked,
+                        };
+                        _ = events.unbounded_send(event);
+                    })
+                });
+                _ = self.shown.unbounded_send(Ok(rx));
+            }
+
+            #[cfg(not(all(unix, not(target_os = "macos"))))]
+            Ok(_) => _ = self.shown.unbounded_send(Ok(rx)),
+
+            Err(err) => {
+                _ = self
+                    .shown
+                    .unbounded_send(Err(NotificationError(err.to_string())));
+            }
+        }
+    }
+}
@@ -0,0 +1,226 @@
+/// Where the computer gets its power from, read with [`DesktopService::power_state`](crate::DesktopService::power_state).
+///
+/// Computers without a battery, and platforms that don't say, are reported as plugged in with no battery.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub struct PowerState {
+    /// If the computer is plugged in
+    pub on_ac: bool,
+    /// If the battery is charging
+    pub charging: bool,
+    /// How full the battery is, from 0 to 1, or `None` if the computer has no battery
+    pub battery_level: Option<f64>,
+}
+
+impl Default for PowerState {
+    fn default() -> Self {
+        Self {
+            on_ac: true,
+            charging: false,
+            battery_level: None,
+        }
+    }
+}
+
+/// Read the power state of the computer. This is cheap enough to call whenever it's needed.
+pub(crate) fn power_state() -> PowerState {
+    platform::power_state()
+}
+
+/// Check the power state in the background and send an event to the event loop when it changes. There is only one
+/// check, however many windows ask for it.
+#[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
+pub(crate) fn watch_power_state(
+    proxy: tao::event_loop::EventLoopProxy<crate::ipc::UserWindowEvent>,
+) {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    static WATCHING: AtomicBool = AtomicBool::new(false);
+    if WATCHING.swap(true, Ordering::Relaxed) {
+        return;
+    }
+
+    // Not every platform notifies apps about power changes, so poll. Being plugged in or out is noticed within a few
+    // seconds, which is soon enough to throttle background work.
+    std::thread::spawn(move || {
+        let mut last = power_state();
+        loop {
+            std::thread::sleep(std::time::Duration::from_secs(5));
+            let state = power_state();
+            if state == last {
+                continue;
+            }
+
+            last = state;
+            let event = crate::ipc::UserWindowEvent::PowerStateChanged(state);
+            if proxy.send_event(event).is_err() {
+                break;
+            }
+        }
+    });
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use super::PowerState;
+    use windows::Win32::System::Power::{GetSystemPowerStatus, SYSTEM_POWER_STATUS};
+
+    // Bits and values of `BatteryFlag`
+    const CHARGING: u8 = 8;
+    const NO_BATTERY: u8 = 128;
+    const UNKNOWN: u8 = 255;
+
+    pub(super) fn power_state() -> PowerState {
+        let mut status = SYSTEM_POWER_STATUS::default();
+        if let Err(err) = unsafe { GetSystemPowerStatus(&mut status) } {
+            tracing::warn!("Failed to read the power state: {err}");
+            return PowerState::default();
+        }
+
+        let has_battery = status.BatteryFlag & NO_BATTERY == 0 && status.BatteryFlag != UNKNOWN;
+        PowerState {
+            on_ac: status.ACLineStatus != 0,
+            charging: has_battery && status.BatteryFlag & CHARGING != 0,
+            battery_level: (has_battery && status.BatteryLifePercent <= 100)
+                .then(|| f64::from(status.BatteryLifePercent) / 100.0),
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use super::PowerState;
+    use std::path::Path;
+
+    pub(super) fn power_state() -> PowerState {
+        let Ok(supplies) = std::fs::read_dir("/sys/class/power_supply") else {
+            return PowerState::default();
+        };
+
+        let read = |supply: &Path, name: &str| {
+            std::fs::read_to_string(supply.join(name))
+                .map(|value| value.trim().to_string())
+                .unwrap_or_default()
+        };
+
+        let (mut mains, mut online) = (false, false);
+        let (mut levels, mut charging, mut discharging) = (Vec::new(), false, false);
+        for supply in supplies.flatten() {
+            let supply = supply.path();
+            match read(&supply, "type").as_str() {
+                "Mains" | "USB" => {
+                    mains = true;
+                    online |= read(&supply, "online") == "1";
+                }
+                // Mice and keyboards report their batteries too, but with the scope of a device
+                "Battery" if read(&supply, "scope") != "Device" => {
+                    if let Ok(capacity) = read(&supply, "capacity").parse::<f64>() {
+                        levels.push((capacity / 100.0).clamp(0.0, 1.0));
+                    }
+                    match read(&supply, "status").as_str() {
+                        "Charging" => charging = true,
+                        "Discharging" => discharging = true,
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        PowerState {
+            // Some laptops don't list their charger, so fall back to what the battery says
+            on_ac: if mains { online } else { !discharging },
+            charging,
+            battery_level: (!levels.is_empty())
+                .then(|| levels.iter().sum::<f64>() / levels.len() as f64),
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use super::PowerState;
+    use core_foundation::{
+        array::{CFArray, CFArrayRef},
+        base::{CFType, CFTypeRef, TCFType},
+        boolean::CFBoolean,
+        dictionary::{CFDictionary, CFDictionaryRef},
+        number::CFNumber,
+        string::{CFString, CFStringRef},
+    };
+
+    #[link(name = "IOKit", kind = "framework")]
+    extern "C" {
+        fn IOPSCopyPowerSourcesInfo() -> CFTypeRef;
+        fn IOPSCopyPowerSourcesList(blob: CFTypeRef) -> CFArrayRef;
+        fn IOPSGetPowerSourceDescription(blob: CFTypeRef, source: CFTypeRef) -> CFDictionaryRef;
+        fn IOPSGetProvidingPowerSourceType(blob: CFTypeRef) -> CFStringRef;
+    }
+
+    pub(super) fn power_state() -> PowerState {
+        unsafe {
+            let info = IOPSCopyPowerSourcesInfo();
+            if info.is_null() {
+                return PowerState::default();
+            }
+            let info = CFType::wrap_under_create_rule(info);
+
+            let providing = IOPSGetProvidingPowerSourceType(info.as_CFTypeRef());
+            let mut state = PowerState {
+                on_ac: providing.is_null()
+                    || CFString::wrap_under_get_rule(providing).to_string() != "Battery Power",
+                ..Default::default()
+            };
+
+            let sources = IOPSCopyPowerSourcesList(info.as_CFTypeRef());
+            if sources.is_null() {
+                return state;
+            }
+            let sources: CFArray<CFType> = CFArray::wrap_under_create_rule(sources);
+
+            // Macs have at most one internal battery, so the first source with a capacity is it
+            for source in sources.iter() {
+                let description =
+                    IOPSGetPowerSourceDescription(info.as_CFTypeRef(), source.as_CFTypeRef());
+                if description.is_null() {
+                    continue;
+                }
+                let description: CFDictionary<CFString, CFType> =
+                    CFDictionary::wrap_under_get_rule(description);
+                let value = |key: &'static str| {
+                    description
+                        .find(CFString::from_static_string(key))
+                        .map(|value| (*value).clone())
+                };
+
+                let capacity = value("Current Capacity").and_then(|v| v.downcast::<CFNumber>());
+                let max = value("Max Capacity").and_then(|v| v.downcast::<CFNumber>());
+                let (Some(capacity), Some(max)) = (
+                    capacity.and_then(|n| n.to_f64()),
+                    max.and_then(|n| n.to_f64()),
+                ) else {
+                    continue;
+                };
+
+                if max > 0.0 {
+                    state.battery_level = Some((capacity / max).clamp(0.0, 1.0));
+                }
+                state.charging = value("Is Charging")
+                    .and_then(|v| v.downcast::<CFBoolean>())
+                    .is_some_and(bool::from);
+                break;
+            }
+
+            state
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux", target_os = "macos")))]
+mod platform {
+    use super::PowerState;
+
+    pub(super) fn power_state() -> PowerState {
+        PowerState::default()
+    }
+}
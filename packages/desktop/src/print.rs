@@ -0,0 +1,216 @@
+use futures_channel::oneshot;
+use std::path::{Path, PathBuf};
+use wry::WebView;
+
+/// An error saving a page with [`DesktopService::print_to_pdf`](crate::DesktopService::print_to_pdf)
+#[derive(Debug, Clone, thiserror::Error)]
+#[non_exhaustive]
+pub enum PrintError {
+    /// The webview of this platform can't save pages as PDFs
+    #[error("Printing to a PDF is not supported on this platform")]
+    Unsupported,
+
+    /// The webview failed to write the PDF
+    #[error("Failed to print to a PDF: {0}")]
+    Failed(String),
+}
+
+pub(crate) type PrintResult = Result<(), PrintError>;
+
+/// Ask the webview to save the page as a PDF at the given path. This needs to be called on the main thread.
+pub(crate) fn print_to_pdf(webview: &WebView, path: &Path) -> oneshot::Receiver<PrintResult> {
+    let (tx, rx) = oneshot::channel();
+
+    // Some webviews resolve relative paths against their own working directory, so we hand them absolute ones
+    match std::path::absolute(path) {
+        Ok(path) => platform::print_to_pdf(webview, path, tx),
+        Err(err) => _ = tx.send(Err(PrintError::Failed(err.to_string()))),
+    }
+
+    rx
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use super::{PathBuf, PrintError, PrintResult};
+    use futures_channel::oneshot;
+    use webview2_com::{
+        Microsoft::Web::WebView2::Win32::{ICoreWebView2PrintSettings, ICoreWebView2_7},
+        PrintToPdfCompletedHandler,
+    };
+    use windows::core::{Interface, HSTRING};
+    use wry::{WebView, WebViewExtWindows};
+
+    pub(super) fn print_to_pdf(webview: &WebView, path: PathBuf, tx: oneshot::Sender<PrintResult>) {
+        let failed = |err: windows::core::Error| PrintError::Failed(err.message().to_string());
+
+        let core = match unsafe { webview.controller().CoreWebView2() } {
+            Ok(core) => core,
+            Err(err) => {
+                _ = tx.send(Err(failed(err)));
+                return;
+            }
+        };
+
+        // Printing to a PDF was added in a later version of WebView2 than the one we require
+        let Ok(core) = core.cast::<ICoreWebView2_7>() else {
+            _ = tx.send(Err(PrintError::Unsupported));
+            return;
+        };
+
+        let tx = std::rc::Rc::new(std::cell::Cell::new(Some(tx)));
+
+        let handler = PrintToPdfCompletedHandler::create(Box::new({
+            let tx = tx.clone();
+            move |result, written| {
+                let result = match result {
+                    Ok(()) if written => Ok(()),
+                    Ok(()) => Err(PrintError::Failed(
+                        "the webview could not write the file".to_string(),
+                    )),
+                    Err(err) => Err(failed(err)),
+                };
+                if let Some(tx) = tx.take() {
+                    _ = tx.send(result);
+                }
+                Ok(())
+            }
+        }));
+
+        let path = HSTRING::from(path.as_path());
+        let started =
+            unsafe { core.PrintToPdf(&path, None::<&ICoreWebView2PrintSettings>, &handler) };
+
+        if let Err(err) = started {
+            if let Some(tx) = tx.take() {
+                _ = tx.send(Err(failed(err)));
+            }
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use super::{PathBuf, PrintError, PrintResult};
+    use futures_channel::oneshot;
+    use std::{cell::Cell, rc::Rc};
+    use webkit2gtk::{PrintOperation, PrintOperationExt};
+    use wry::{WebView, WebViewExtUnix};
+
+    pub(super) fn print_to_pdf(webview: &WebView, path: PathBuf, tx: oneshot::Sender<PrintResult>) {
+        let uri = match webkit2gtk::glib::filename_to_uri(&path, None) {
+            Ok(uri) => uri,
+            Err(err) => {
+                _ = tx.send(Err(PrintError::Failed(err.to_string())));
+                return;
+            }
+        };
+
+        // GTK saves PDFs through its print-to-file backend
+        let settings = gtk::PrintSettings::new();
+        settings.set_printer("Print to File");
+        settings.set("output-file-format", Some("pdf"));
+        settings.set("output-uri", Some(uri.as_str()));
+
+        let operation = PrintOperation::new(&webview.webview());
+        operation.set_print_settings(&settings);
+
+        // A failed print operation reports the failure and then finishes, so whichever comes first wins
+        let tx = Rc::new(Cell::new(Some(tx)));
+        operation.connect_failed({
+            let tx = tx.clone();
+            move |_, err| {
+                if let Some(tx) = tx.take() {
+                    _ = tx.send(Err(PrintError::Failed(err.to_string())));
+                }
+            }
+        });
+        operation.connect_finished(move |_| {
+            if let Some(tx) = tx.take() {
+                _ = tx.send(Ok(()));
+            }
+        });
+
+        operation.print();
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use super::{PathBuf, PrintError, PrintResult};
+    use block::ConcreteBlock;
+    use futures_channel::oneshot;
+    use objc::{
+        msg_send,
+        runtime::{Object, BOOL, YES},
+        sel, sel_impl,
+    };
+    use std::cell::Cell;
+    use wry::{WebView, WebViewExtMacOS};
+
+    pub(super) fn print_to_pdf(webview: &WebView, path: PathBuf, tx: oneshot::Sender<PrintResult>) {
+        let webview: *mut Object = webview.webview();
+
+        // This was added in macOS 11
+        let supported: BOOL = unsafe {
+            msg_send![webview, respondsToSelector: sel!(createPDFWithConfiguration:completionHandler:)]
+        };
+        if supported != YES {
+            _ = tx.send(Err(PrintError::Unsupported));
+            return;
+        }
+
+        let tx = Cell::new(Some(tx));
+
+        let handler = ConcreteBlock::new(move |data: *mut Object, error: *mut Object| {
+            let Some(tx) = tx.take() else {
+                return;
+            };
+
+            let result = if data.is_null() {
+                Err(PrintError::Failed(unsafe { error_description(error) }))
+            } else {
+                let bytes = unsafe {
+                    let bytes: *const u8 = msg_send![data, bytes];
+                    let length: usize = msg_send![data, length];
+                    std::slice::from_raw_parts(bytes, length)
+                };
+                std::fs::write(&path, bytes).map_err(|err| PrintError::Failed(err.to_string()))
+            };
+            _ = tx.send(result);
+        })
+        .copy();
+
+        unsafe {
+            let configuration: *mut Object = std::ptr::null_mut();
+            let _: () = msg_send![webview, createPDFWithConfiguration: configuration completionHandler: &*handler];
+        }
+    }
+
+    unsafe fn error_description(error: *mut Object) -> String {
+        if error.is_null() {
+            return "the webview returned no data".to_string();
+        }
+
+        let description: *mut Object = msg_send![error, localizedDescription];
+        let utf8: *const std::os::raw::c_char = msg_send![description, UTF8String];
+        std::ffi::CStr::from_ptr(utf8)
+            .to_string_lossy()
+            .into_owned()
+    }
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux", target_os = "macos")))]
+mod platform {
+    use super::{PathBuf, PrintError, PrintResult};
+    use futures_channel::oneshot;
+    use wry::WebView;
+
+    pub(super) fn print_to_pdf(
+        _webview: &WebView,
+        _path: PathBuf,
+        tx: oneshot::Sender<PrintResult>,
+    ) {
+        _ = tx.send(Err(PrintError::Unsupported));
+    }
+}
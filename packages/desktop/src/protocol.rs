@@ -35,20 +35,26 @@ pub(super) fn desktop_handler(
     edit_state: &WebviewEdits,
     custom_head: Option<String>,
     custom_index: Option<String>,
+    csp: Option<&str>,
     root_name: &str,
     headless: bool,
 ) {
     // Try to serve the index file first
-    if let Some(index_bytes) =
-        index_request(&request, custom_head, custom_index, root_name, headless)
-    {
+    if let Some(index_bytes) = index_request(
+        &request,
+        custom_head,
+        custom_index,
+        csp,
+        root_name,
+        headless,
+    ) {
         return responder.respond(index_bytes);
     }
 
     // If the request is asking for edits (ie binary protocol streaming), do that
     let trimmed_uri = request.uri().path().trim_matches('/');
     if trimmed_uri == "__edits" {
-        return edit_state.wry_queue.handle_request(responder);
+        return edit_state.handle_edits_request(responder);
     }
 
     // If the request is asking for an event response, do that
@@ -134,6 +140,7 @@ fn index_request(
     request: &Request<Vec<u8>>,
     custom_head: Option<String>,
     custom_index: Option<String>,
+    csp: Option<&str>,
     root_name: &str,
     headless: bool,
 ) -> Option<Response<Vec<u8>>> {
@@ -152,12 +159,25 @@ fn index_request(
         index.insert_str(index.find("</head>").expect("Head element to exist"), &head);
     }
 
+    // The policy only applies to the elements after it, so it goes right at the start of the head
+    let nonce = csp.map(|_| csp_nonce());
+    if let (Some(csp), Some(nonce)) = (csp, &nonce) {
+        let head_open = find_head_open(&index).expect("Head element to exist");
+        let head_content =
+            head_open + index[head_open..].find('>').expect("Head element to exist") + 1;
+        let csp = csp.replace("{nonce}", nonce).replace('"', "&quot;");
+        index.insert_str(
+            head_content,
+            &format!(r#"<meta http-equiv="Content-Security-Policy" content="{csp}">"#),
+        );
+    }
+
     // Inject our module loader by looking for a body tag
     // A failure mode here, obviously, is if the user provided a custom index without a body tag
     // Might want to document this
     index.insert_str(
         index.find("</body>").expect("Body element to exist"),
-        &module_loader(root_name, headless),
+        &module_loader(root_name, headless, nonce.as_deref()),
     );
 
     Response::builder()
@@ -167,16 +187,33 @@ fn index_request(
         .ok()
 }
 
+/// Find the opening `<head>` tag of an index page, without mistaking a `<header>` element for it
+pub(crate) fn find_head_open(index: &str) -> Option<usize> {
+    index
+        .match_indices("<head")
+        .map(|(start, _)| start)
+        .find(|start| {
+            index[start + "<head".len()..]
+                .chars()
+                .next()
+                .is_some_and(|next| next == '>' || next.is_ascii_whitespace())
+        })
+}
+
 /// Construct the inline script that boots up the page and bridges the webview with rust code.
 ///
 /// The arguments here:
 /// - root_name: the root element (by Id) that we stream edits into
 /// - headless: is this page being loaded but invisible? Important because not all windows are visible and the
 ///             interpreter can't connect until the window is ready.
-fn module_loader(root_id: &str, headless: bool) -> String {
+/// - nonce: the nonce the content security policy lets inline scripts run with, if there is a policy
+fn module_loader(root_id: &str, headless: bool, nonce: Option<&str>) -> String {
+    let nonce = nonce
+        .map(|nonce| format!(r#" nonce="{nonce}""#))
+        .unwrap_or_default();
     format!(
         r#"
-<script type="module">
+<script type="module"{nonce}>
     // Bring the sledgehammer code
     {SLEDGEHAMMER_JS}
 
@@ -196,7 +233,7 @@ fn module_loader(root_id: &str, headless: bool) -> String {
         window.interpreter.waitForRequest({headless});
     }}
 </script>
-<script type="module">
+<script type="module"{nonce}>
     // Include the code for eval
     {NATIVE_EVAL_JS}
 </script>
@@ -204,6 +241,16 @@ fn module_loader(root_id: &str, headless: bool) -> String {
     )
 }
 
+/// Generate a random nonce for the inline scripts of a single page load
+fn csp_nonce() -> String {
+    use base64::Engine;
+    use rand::RngCore;
+
+    let mut bytes = [0; 16];
+    rand::rngs::OsRng.fill_bytes(&mut bytes);
+    base64::prelude::BASE64_STANDARD.encode(bytes)
+}
+
 /// Get the asset directory, following tauri/cargo-bundles directory discovery approach
 ///
 /// Currently supports:
@@ -320,3 +367,21 @@ pub(crate) fn to_java_load_asset(filepath: &str) -> Option<Vec<u8>> {
         Some(asset.buffer().unwrap().to_vec())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::find_head_open;
+
+    #[test]
+    fn head_is_not_confused_with_header() {
+        let index =
+            r#"<html><header class="top"></header><head lang="en"></head><body></body></html>"#;
+        assert_eq!(find_head_open(index), index.find("<head lang"));
+
+        assert_eq!(find_head_open("<html><head></head></html>"), Some(6));
+        assert_eq!(
+            find_head_open("<html><header></header><body></body></html>"),
+            None
+        );
+    }
+}
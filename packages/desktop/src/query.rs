@@ -1,10 +1,14 @@
-use crate::{DesktopContext, WeakDesktopContext};
+use crate::{ipc::truncate_for_log, DesktopContext, WeakDesktopContext};
 use futures_util::{FutureExt, StreamExt};
 use generational_box::Owner;
 use serde::{de::DeserializeOwned, Deserialize};
 use serde_json::Value;
 use slab::Slab;
-use std::{cell::RefCell, rc::Rc};
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    sync::atomic::{AtomicUsize, Ordering},
+};
 use thiserror::Error;
 
 /// Tracks what query ids are currently active
@@ -100,7 +104,53 @@ impl QueryEngine {
                 }}
             }})();"#
         )) {
-            tracing::warn!("Query error: {err}");
+            log_script_error(request_id, script, err);
+        }
+
+        Query {
+            id: request_id,
+            receiver: rx,
+            return_receiver: Some(return_rx),
+            desktop: Rc::downgrade(&context),
+            phantom: std::marker::PhantomData,
+        }
+    }
+
+    /// Creates a query that resolves to the value of a single JavaScript expression.
+    ///
+    /// Unlike [`QueryEngine::new_query`], this doesn't rely on the eval bindings the index page loads, so it also works
+    /// after the webview navigated to a page that isn't ours.
+    pub fn new_expression_query<V: DeserializeOwned>(
+        &self,
+        expression: &str,
+        context: DesktopContext,
+    ) -> Query<V> {
+        let (tx, rx) = futures_channel::mpsc::unbounded();
+        let (return_tx, return_rx) = futures_channel::oneshot::channel();
+        let request_id = self.active_requests.slab.borrow_mut().insert(QueryEntry {
+            channel_sender: tx,
+            return_sender: Some(return_tx),
+            owner: None,
+        });
+
+        // Return the value and then drop the query right away since nothing else can be sent to it
+        if let Err(err) = context.webview.evaluate_script(&format!(
+            r#"(function(){{
+                let post = function(data) {{
+                    window.ipc.postMessage(JSON.stringify({{
+                        "method": "query",
+                        "params": {{ "id": {request_id}, "data": data }}
+                    }}));
+                }};
+                try {{
+                    post({{ "method": "return", "data": ({expression}) }});
+                }} catch (error) {{
+                    post({{ "method": "return_error", "data": `Error running JS: ${{error}}` }});
+                }}
+                post({{ "method": "drop" }});
+            }})();"#
+        )) {
+            log_script_error(request_id, expression, err);
         }
 
         Query {
@@ -139,6 +189,19 @@ impl QueryEngine {
     }
 }
 
+/// How many scripts the webview refused to run, so repeated failures stand out in the logs
+static FAILED_SCRIPTS: AtomicUsize = AtomicUsize::new(0);
+
+/// Log a query the webview failed to start, without dumping a huge script into the logs
+fn log_script_error(request_id: usize, script: &str, err: wry::Error) {
+    let failures = FAILED_SCRIPTS.fetch_add(1, Ordering::Relaxed) + 1;
+    tracing::warn!(
+        "Query {request_id} failed to run ({failures} failed so far): {err}. The script is {} bytes long: {}",
+        script.len(),
+        truncate_for_log(script)
+    );
+}
+
 pub(crate) struct Query<V: DeserializeOwned> {
     desktop: WeakDesktopContext,
     receiver: futures_channel::mpsc::UnboundedReceiver<Value>,
@@ -0,0 +1,112 @@
+use dioxus_core::{AttributeValue, ElementId, Template, WriteMutations};
+use std::time::Duration;
+
+/// Receives timing information about the renders of a window.
+///
+/// Register one with [`Config::with_render_observer`](crate::Config::with_render_observer) to log slow frames or
+/// export metrics. When no observer is registered, none of this is measured.
+pub trait RenderObserver {
+    /// Called right before the virtual dom renders
+    fn on_render_start(&self) {}
+
+    /// Called after the virtual dom rendered, with how long the render took and how many mutations it produced
+    fn on_render_complete(&self, _duration: Duration, _mutation_count: usize) {}
+
+    /// Called once the webview has applied a batch of edits, with the time since the batch was sent to it
+    fn on_edits_flushed(&self, _duration: Duration) {}
+}
+
+/// Forwards mutations to another writer while counting them
+pub(crate) struct CountingMutations<'a, M> {
+    inner: &'a mut M,
+    pub(crate) count: usize,
+}
+
+impl<'a, M: WriteMutations> CountingMutations<'a, M> {
+    pub(crate) fn new(inner: &'a mut M) -> Self {
+        Self { inner, count: 0 }
+    }
+}
+
+impl<M: WriteMutations> WriteMutations for CountingMutations<'_, M> {
+    fn append_children(&mut self, id: ElementId, m: usize) {
+        self.count += 1;
+        self.inner.append_children(id, m)
+    }
+
+    fn assign_node_id(&mut self, path: &'static [u8], id: ElementId) {
+        self.count += 1;
+        self.inner.assign_node_id(path, id)
+    }
+
+    fn create_placeholder(&mut self, id: ElementId) {
+        self.count += 1;
+        self.inner.create_placeholder(id)
+    }
+
+    fn create_text_node(&mut self, value: &str, id: ElementId) {
+        self.count += 1;
+        self.inner.create_text_node(value, id)
+    }
+
+    fn load_template(&mut self, template: Template, index: usize, id: ElementId) {
+        self.count += 1;
+        self.inner.load_template(template, index, id)
+    }
+
+    fn replace_node_with(&mut self, id: ElementId, m: usize) {
+        self.count += 1;
+        self.inner.replace_node_with(id, m)
+    }
+
+    fn replace_placeholder_with_nodes(&mut self, path: &'static [u8], m: usize) {
+        self.count += 1;
+        self.inner.replace_placeholder_with_nodes(path, m)
+    }
+
+    fn insert_nodes_after(&mut self, id: ElementId, m: usize) {
+        self.count += 1;
+        self.inner.insert_nodes_after(id, m)
+    }
+
+    fn insert_nodes_before(&mut self, id: ElementId, m: usize) {
+        self.count += 1;
+        self.inner.insert_nodes_before(id, m)
+    }
+
+    fn set_attribute(
+        &mut self,
+        name: &'static str,
+        ns: Option<&'static str>,
+        value: &AttributeValue,
+        id: ElementId,
+    ) {
+        self.count += 1;
+        self.inner.set_attribute(name, ns, value, id)
+    }
+
+    fn set_node_text(&mut self, value: &str, id: ElementId) {
+        self.count += 1;
+        self.inner.set_node_text(value, id)
+    }
+
+    fn create_event_listener(&mut self, name: &'static str, id: ElementId) {
+        self.count += 1;
+        self.inner.create_event_listener(name, id)
+    }
+
+    fn remove_event_listener(&mut self, name: &'static str, id: ElementId) {
+        self.count += 1;
+        self.inner.remove_event_listener(name, id)
+    }
+
+    fn remove_node(&mut self, id: ElementId) {
+        self.count += 1;
+        self.inner.remove_node(id)
+    }
+
+    fn push_root(&mut self, id: ElementId) {
+        self.count += 1;
+        self.inner.push_root(id)
+    }
+}
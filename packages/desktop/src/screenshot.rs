@@ -0,0 +1,227 @@
+use futures_channel::oneshot;
+use wry::WebView;
+
+/// An error capturing a screenshot with [`DesktopService::capture_screenshot`](crate::DesktopService::capture_screenshot)
+#[derive(Debug, Clone, thiserror::Error)]
+#[non_exhaustive]
+pub enum CaptureError {
+    /// The window is minimized or hidden, so there is nothing on screen to capture
+    #[error("The window is minimized or hidden")]
+    NotVisible,
+
+    /// The webview of this platform can't take screenshots
+    #[error("Capturing the webview is not supported on this platform")]
+    Unsupported,
+
+    /// The webview failed to take the screenshot
+    #[error("Failed to capture the webview: {0}")]
+    Failed(String),
+}
+
+pub(crate) type CaptureResult = Result<Vec<u8>, CaptureError>;
+
+/// Ask the webview for a PNG of what it is showing. This needs to be called on the main thread.
+pub(crate) fn capture(webview: &WebView) -> oneshot::Receiver<CaptureResult> {
+    let (tx, rx) = oneshot::channel();
+    platform::capture(webview, tx);
+    rx
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use super::{CaptureError, CaptureResult};
+    use futures_channel::oneshot;
+    use webview2_com::{
+        CapturePreviewCompletedHandler,
+        Microsoft::Web::WebView2::Win32::COREWEBVIEW2_CAPTURE_PREVIEW_IMAGE_FORMAT_PNG,
+    };
+    use windows::Win32::{
+        System::Com::{IStream, STREAM_SEEK_SET},
+        UI::Shell::SHCreateMemStream,
+    };
+    use wry::{WebView, WebViewExtWindows};
+
+    pub(super) fn capture(webview: &WebView, tx: oneshot::Sender<CaptureResult>) {
+        let failed = |err: windows::core::Error| CaptureError::Failed(err.message().to_string());
+
+        let Some(stream) = (unsafe { SHCreateMemStream(None) }) else {
+            _ = tx.send(Err(CaptureError::Failed(
+                "could not allocate a stream for the image".to_string(),
+            )));
+            return;
+        };
+
+        let core = match unsafe { webview.controller().CoreWebView2() } {
+            Ok(core) => core,
+            Err(err) => {
+                _ = tx.send(Err(failed(err)));
+                return;
+            }
+        };
+
+        // WebView2 only hands the sender back through the handler, so keep it here in case starting the capture fails
+        let tx = std::rc::Rc::new(std::cell::Cell::new(Some(tx)));
+
+        let handler = CapturePreviewCompletedHandler::create(Box::new({
+            let stream = stream.clone();
+            let tx = tx.clone();
+            move |result| {
+                let bytes = result.and_then(|_| read_stream(&stream)).map_err(failed);
+                if let Some(tx) = tx.take() {
+                    _ = tx.send(bytes);
+                }
+                Ok(())
+            }
+        }));
+
+        let started = unsafe {
+            core.CapturePreview(
+                COREWEBVIEW2_CAPTURE_PREVIEW_IMAGE_FORMAT_PNG,
+                &stream,
+                &handler,
+            )
+        };
+
+        if let Err(err) = started {
+            if let Some(tx) = tx.take() {
+                _ = tx.send(Err(failed(err)));
+            }
+        }
+    }
+
+    fn read_stream(stream: &IStream) -> windows::core::Result<Vec<u8>> {
+        unsafe { stream.Seek(0, STREAM_SEEK_SET, None)? };
+
+        let mut bytes = Vec::new();
+        let mut chunk = [0u8; 64 * 1024];
+        loop {
+            let mut read = 0;
+            unsafe {
+                stream
+                    .Read(
+                        chunk.as_mut_ptr().cast(),
+                        chunk.len() as u32,
+                        Some(&mut read),
+                    )
+                    .ok()?
+            };
+
+            if read == 0 {
+                return Ok(bytes);
+            }
+            bytes.extend_from_slice(&chunk[..read as usize]);
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use super::{CaptureError, CaptureResult};
+    use futures_channel::oneshot;
+    use webkit2gtk::{SnapshotOptions, SnapshotRegion, WebViewExt};
+    use wry::{WebView, WebViewExtUnix};
+
+    pub(super) fn capture(webview: &WebView, tx: oneshot::Sender<CaptureResult>) {
+        webview.webview().snapshot(
+            SnapshotRegion::Visible,
+            SnapshotOptions::NONE,
+            None::<&webkit2gtk::gio::Cancellable>,
+            move |surface| {
+                let bytes = surface
+                    .map_err(|err| CaptureError::Failed(err.to_string()))
+                    .and_then(|surface| {
+                        let mut bytes = Vec::new();
+                        surface
+                            .write_to_png(&mut bytes)
+                            .map_err(|err| CaptureError::Failed(err.to_string()))?;
+                        Ok(bytes)
+                    });
+                _ = tx.send(bytes);
+            },
+        );
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use super::{CaptureError, CaptureResult};
+    use block::ConcreteBlock;
+    use futures_channel::oneshot;
+    use objc::{class, msg_send, runtime::Object, sel, sel_impl};
+    use std::cell::Cell;
+    use wry::{WebView, WebViewExtMacOS};
+
+    /// `NSBitmapImageFileTypePNG`
+    const PNG_FILE_TYPE: u64 = 4;
+
+    pub(super) fn capture(webview: &WebView, tx: oneshot::Sender<CaptureResult>) {
+        let tx = Cell::new(Some(tx));
+
+        let handler = ConcreteBlock::new(move |image: *mut Object, error: *mut Object| {
+            let Some(tx) = tx.take() else {
+                return;
+            };
+
+            let result = if image.is_null() {
+                Err(CaptureError::Failed(unsafe { error_description(error) }))
+            } else {
+                unsafe { png_bytes(image) }
+            };
+            _ = tx.send(result);
+        })
+        .copy();
+
+        unsafe {
+            let webview: *mut Object = webview.webview();
+            let configuration: *mut Object = std::ptr::null_mut();
+            let _: () = msg_send![webview, takeSnapshotWithConfiguration: configuration completionHandler: &*handler];
+        }
+    }
+
+    /// Encode an `NSImage` as a PNG
+    unsafe fn png_bytes(image: *mut Object) -> CaptureResult {
+        let tiff: *mut Object = msg_send![image, TIFFRepresentation];
+        let rep: *mut Object = msg_send![class!(NSBitmapImageRep), imageRepWithData: tiff];
+        if rep.is_null() {
+            return Err(CaptureError::Failed(
+                "could not read the snapshot image".to_string(),
+            ));
+        }
+
+        let properties: *mut Object = msg_send![class!(NSDictionary), dictionary];
+        let png: *mut Object =
+            msg_send![rep, representationUsingType: PNG_FILE_TYPE properties: properties];
+        if png.is_null() {
+            return Err(CaptureError::Failed(
+                "could not encode the snapshot as a PNG".to_string(),
+            ));
+        }
+
+        let bytes: *const u8 = msg_send![png, bytes];
+        let length: usize = msg_send![png, length];
+        Ok(std::slice::from_raw_parts(bytes, length).to_vec())
+    }
+
+    unsafe fn error_description(error: *mut Object) -> String {
+        if error.is_null() {
+            return "the webview returned no image".to_string();
+        }
+
+        let description: *mut Object = msg_send![error, localizedDescription];
+        let utf8: *const std::os::raw::c_char = msg_send![description, UTF8String];
+        std::ffi::CStr::from_ptr(utf8)
+            .to_string_lossy()
+            .into_owned()
+    }
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux", target_os = "macos")))]
+mod platform {
+    use super::{CaptureError, CaptureResult};
+    use futures_channel::oneshot;
+    use wry::WebView;
+
+    pub(super) fn capture(_webview: &WebView, tx: oneshot::Sender<CaptureResult>) {
+        _ = tx.send(Err(CaptureError::Unsupported));
+    }
+}
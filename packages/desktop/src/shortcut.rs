@@ -41,6 +41,8 @@ impl ShortcutHandle {
 pub enum ShortcutRegistryError {
     /// The shortcut is invalid.
     InvalidShortcut(String),
+    /// The operating system refused the shortcut, usually because another app already claimed it.
+    AlreadyRegistered(String),
     /// An unknown error occurred.
     Other(Rc<dyn std::error::Error>),
 }
@@ -93,6 +95,12 @@ impl ShortcutRegistry {
             HotkeyError::HotKeyParseError(shortcut) => {
                 ShortcutRegistryError::InvalidShortcut(shortcut)
             }
+            HotkeyError::AlreadyRegistered(hotkey) => {
+                ShortcutRegistryError::AlreadyRegistered(hotkey.into_string())
+            }
+            HotkeyError::FailedToRegister(reason) => {
+                ShortcutRegistryError::AlreadyRegistered(reason)
+            }
             err => ShortcutRegistryError::Other(Rc::new(err)),
         })?;
 
@@ -0,0 +1,100 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use tao::window::{ProgressBarState, ProgressState, Window};
+
+/// Show the progress of a long task on the dock icon or taskbar button of the app, or hide it with `None`.
+/// This needs to be called on the main thread.
+pub(crate) fn set_progress(window: &Window, progress: Option<f64>) {
+    if cfg!(any(target_os = "ios", target_os = "android")) {
+        unsupported(
+            &PROGRESS_UNSUPPORTED,
+            "Progress bars are not supported on this platform",
+        );
+        return;
+    }
+
+    let state = match progress {
+        Some(progress) => ProgressBarState {
+            state: Some(ProgressState::Normal),
+            // NaN is clamped to NaN, which casts to 0
+            progress: Some((progress.clamp(0.0, 1.0) * 100.0).round() as u64),
+            desktop_filename: desktop_filename(),
+        },
+        None => ProgressBarState {
+            state: Some(ProgressState::None),
+            progress: None,
+            desktop_filename: desktop_filename(),
+        },
+    };
+
+    window.set_progress_bar(state);
+}
+
+/// Show a short label on the dock icon of the app, or remove it with `None`. This needs to be called on the main
+/// thread.
+pub(crate) fn set_badge(window: &Window, badge: Option<String>) {
+    #[cfg(target_os = "macos")]
+    {
+        use tao::platform::macos::WindowExtMacOS;
+        window.set_badge_label(badge);
+    }
+
+    // The Unity launcher and iOS only show numbers
+    #[cfg(target_os = "linux")]
+    {
+        use tao::platform::unix::WindowExtUnix;
+        match badge.as_deref().map(str::parse::<i64>) {
+            Some(Ok(count)) => window.set_badge_count(Some(count), desktop_filename()),
+            Some(Err(_)) => unsupported(
+                &BADGE_UNSUPPORTED,
+                "Badges on Linux can only be numbers, ignoring the badge",
+            ),
+            None => window.set_badge_count(None, desktop_filename()),
+        }
+    }
+
+    #[cfg(target_os = "ios")]
+    {
+        use tao::platform::ios::WindowExtIOS;
+        match badge.as_deref().map(str::parse::<i32>) {
+            Some(Ok(count)) => window.set_badge_count(count),
+            Some(Err(_)) => unsupported(
+                &BADGE_UNSUPPORTED,
+                "Badges on iOS can only be numbers, ignoring the badge",
+            ),
+            None => window.set_badge_count(0),
+        }
+    }
+
+    // Windows can only overlay an icon on the taskbar button, not text
+    #[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "ios")))]
+    {
+        _ = (window, badge);
+        unsupported(
+            &BADGE_UNSUPPORTED,
+            "Badges are not supported on this platform",
+        );
+    }
+}
+
+static PROGRESS_UNSUPPORTED: AtomicBool = AtomicBool::new(false);
+#[cfg(not(target_os = "macos"))]
+static BADGE_UNSUPPORTED: AtomicBool = AtomicBool::new(false);
+
+/// Apps set their progress often, so only tell the developer once that it does nothing
+fn unsupported(logged: &AtomicBool, message: &str) {
+    if !logged.swap(true, Ordering::Relaxed) {
+        tracing::debug!("{message}");
+    }
+}
+
+/// The Unity launcher finds the entry of the app by the name of its desktop file, which we guess from the name of the
+/// executable
+fn desktop_filename() -> Option<String> {
+    if !cfg!(target_os = "linux") {
+        return None;
+    }
+
+    let exe = std::env::current_exe().ok()?;
+    let name = exe.file_stem()?.to_str()?;
+    Some(format!("{name}.desktop"))
+}
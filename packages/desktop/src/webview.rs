@@ -4,12 +4,22 @@ use crate::menubar::DioxusMenu;
 use crate::{
     app::SharedContext,
     assets::AssetHandlerRegistry,
+    commands::INVOKE_SCRIPT,
+    config::{NavigationPolicy, OpenedWindowSettings},
+    context_menu::CUSTOM_MENU_SCRIPT,
+    download::{self, DownloadCompleted, DownloadRequest},
     edits::WryQueue,
+    events::handle_event_catching_panics,
     file_upload::{NativeFileEngine, NativeFileHover},
-    ipc::UserWindowEvent,
+    ipc::{InvalidMessages, IpcMessage, UserWindowEvent},
+    launch::LaunchError,
+    media::MEDIA_PREFERENCES_SCRIPT,
+    network::ONLINE_STATUS_SCRIPT,
     protocol,
+    render_observer::CountingMutations,
     waker::tao_waker,
-    Config, DesktopContext, DesktopService,
+    AutoplayPolicy, Config, DesktopContext, DesktopService, ExternalLinkPolicy, NavigationDecision,
+    RenderObserver,
 };
 use crate::{document::DesktopDocument, WeakDesktopContext};
 use base64::prelude::BASE64_STANDARD;
@@ -19,27 +29,48 @@ use dioxus_history::{History, MemoryHistory};
 use dioxus_hooks::to_owned;
 use dioxus_html::{HasFileData, HtmlEvent, PlatformEventData};
 use futures_util::{pin_mut, FutureExt};
-use std::cell::OnceCell;
+use std::cell::{OnceCell, RefCell};
 use std::sync::Arc;
 use std::{rc::Rc, task::Waker};
 use wry::{DragDropEvent, RequestAsyncResponder, WebContext, WebViewBuilder};
 
 #[derive(Clone)]
 pub(crate) struct WebviewEdits {
-    runtime: Rc<Runtime>,
+    // Shared with the protocol handler, and swapped out when the root component is replaced
+    runtime: Rc<RefCell<Rc<Runtime>>>,
     pub wry_queue: WryQueue,
+    pub render_observer: Option<Rc<dyn RenderObserver>>,
     desktop_context: Rc<OnceCell<WeakDesktopContext>>,
+    invalid_messages: Rc<InvalidMessages>,
 }
 
 impl WebviewEdits {
-    fn new(runtime: Rc<Runtime>, wry_queue: WryQueue) -> Self {
+    fn new(
+        runtime: Rc<Runtime>,
+        wry_queue: WryQueue,
+        render_observer: Option<Rc<dyn RenderObserver>>,
+        invalid_messages: Rc<InvalidMessages>,
+    ) -> Self {
         Self {
-            runtime,
+            runtime: Rc::new(RefCell::new(runtime)),
             wry_queue,
+            render_observer,
             desktop_context: Default::default(),
+            invalid_messages,
         }
     }
 
+    pub fn handle_edits_request(&self, responder: RequestAsyncResponder) {
+        let flushed = self.wry_queue.handle_request(responder);
+        if let (Some(observer), Some(duration)) = (&self.render_observer, flushed) {
+            observer.on_edits_flushed(duration);
+        }
+    }
+
+    fn set_runtime(&self, runtime: Rc<Runtime>) {
+        *self.runtime.borrow_mut() = runtime;
+    }
+
     fn set_desktop_context(&self, context: WeakDesktopContext) {
         _ = self.desktop_context.set(context);
     }
@@ -49,9 +80,15 @@ impl WebviewEdits {
         request: wry::http::Request<Vec<u8>>,
         responder: wry::RequestAsyncResponder,
     ) {
-        let body = self
-            .try_handle_event(request)
-            .expect("Writing bodies to succeed");
+        // If the event couldn't be decoded we still need to respond, otherwise the interpreter will block forever
+        // waiting on the synchronous request. Responding with the default response lets the browser run its default action.
+        let body = match self.try_handle_event(request) {
+            Ok(body) => body,
+            Err(err) => {
+                tracing::error!("Failed to handle event from the webview: {err}");
+                serde_json::to_vec(&SynchronousEventResponse::default()).unwrap_or_default()
+            }
+        };
         responder.respond(wry::http::Response::new(body))
     }
 
@@ -86,11 +123,10 @@ impl WebviewEdits {
                 self.handle_html_event(event)
             }
             Err(err) => {
-                tracing::error!(
-                    "Error parsing user_event: {:?}.Contents: {:?}, raw: {:#?}",
+                self.invalid_messages.drop_message(
+                    "user event",
                     err,
-                    String::from_utf8(request.body().to_vec()),
-                    request
+                    &String::from_utf8_lossy(&data_from_header),
                 );
                 SynchronousEventResponse::new(false)
             }
@@ -115,7 +151,10 @@ impl WebviewEdits {
             return Default::default();
         };
 
-        let desktop_context = desktop_context.upgrade().unwrap();
+        let Some(desktop_context) = desktop_context.upgrade() else {
+            tracing::error!("Tried to handle event after the desktop context was dropped");
+            return Default::default();
+        };
 
         let query = desktop_context.query.clone();
         let recent_file = desktop_context.file_hover.clone();
@@ -129,10 +168,9 @@ impl WebviewEdits {
             dioxus_html::EventData::Drag(ref drag) => {
                 // we want to override this with a native file engine, provided by the most recent drag event
                 if drag.files().is_some() {
-                    let file_event = recent_file.current().unwrap();
-                    let paths = match file_event {
-                        wry::DragDropEvent::Enter { paths, .. } => paths,
-                        wry::DragDropEvent::Drop { paths, .. } => paths,
+                    let paths = match recent_file.current() {
+                        Some(wry::DragDropEvent::Enter { paths, .. }) => paths,
+                        Some(wry::DragDropEvent::Drop { paths, .. }) => paths,
                         _ => vec![],
                     };
                     Rc::new(PlatformEventData::new(Box::new(DesktopFileDragEvent {
@@ -147,7 +185,8 @@ impl WebviewEdits {
         };
 
         let event = dioxus_core::Event::new(as_any, bubbles);
-        self.runtime.handle_event(&name, event.clone(), element);
+        let runtime = self.runtime.borrow().clone();
+        handle_event_catching_panics(&runtime, &name, event.clone(), element);
 
         // Get the response from the event
         SynchronousEventResponse::new(!event.default_action_enabled())
@@ -160,6 +199,24 @@ pub(crate) struct WebviewInstance {
     pub desktop_context: DesktopContext,
     pub waker: Waker,
 
+    /// Whether closing this window exits the app, overriding the app-wide close behaviour
+    pub exit_app_on_close: Option<bool>,
+
+    /// What to do with links that leave the app
+    pub external_link_policy: ExternalLinkPolicy,
+
+    /// The settings windows opened by this one for its navigation policy inherit
+    pub opened_window_settings: OpenedWindowSettings,
+
+    /// If the interpreter has finished loading and asked for the first render
+    pub initialized: bool,
+
+    /// A navigation requested before the page finished loading, applied once it has
+    pending_navigation: Option<Navigation>,
+
+    /// The headers set with [`Config::with_headers`], sent with every document the window loads
+    headers: wry::http::HeaderMap,
+
     // Wry assumes the webcontext is alive for the lifetime of the webview.
     // We need to keep the webcontext alive, otherwise the webview will crash
     _web_context: WebContext,
@@ -170,6 +227,10 @@ pub(crate) struct WebviewInstance {
     //
     // This would be a good thing for someone looking to contribute to fix.
     _menu: Option<DioxusMenu>,
+
+    /// The native menu shown when the page is right-clicked, if the window has a custom one
+    #[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
+    pub context_menu: Option<muda::Menu>,
 }
 
 impl WebviewInstance {
@@ -179,6 +240,7 @@ impl WebviewInstance {
         shared: Rc<SharedContext>,
     ) -> WebviewInstance {
         let mut window = cfg.window.clone();
+        let opened_window_settings = cfg.opened_window_settings();
 
         // tao makes small windows for some reason, make them bigger on desktop
         //
@@ -203,8 +265,56 @@ impl WebviewInstance {
             ));
         }
 
+        // An opaque webview covers the window, so a transparent window would just look broken
+        let transparent = cfg.window.window.transparent;
+        if transparent && cfg!(target_os = "macos") && !cfg!(feature = "transparent") {
+            tracing::error!("Transparent windows on macOS need the `transparent` feature of dioxus-desktop, the webview will be opaque");
+        }
+
+        // Paint the window in the same color as the webview, so resizing or showing the window before the webview
+        // draws doesn't flash white. Windows ignores the alpha of the window color, which would make transparent
+        // windows opaque, so they are left to the webview.
+        if let Some(color) = cfg.background_color {
+            if !(cfg!(target_os = "windows") && transparent) {
+                window = window.with_background_color(color);
+            }
+        }
+
+        #[cfg(target_os = "windows")]
+        {
+            use tao::platform::windows::WindowBuilderExtWindows;
+            window = window.with_skip_taskbar(cfg.skip_taskbar);
+            window = window.with_undecorated_shadow(cfg.window_shadow);
+        }
+        #[cfg(target_os = "macos")]
+        if !cfg.window.window.decorations {
+            use tao::platform::macos::WindowBuilderExtMacOS;
+            window = window.with_has_shadow(cfg.window_shadow);
+        }
+        #[cfg(target_os = "linux")]
+        {
+            use tao::platform::unix::WindowBuilderExtUnix;
+            window = window.with_skip_taskbar(cfg.skip_taskbar);
+        }
+
         let window = window.build(&shared.target).unwrap();
 
+        // macOS can only hide the whole app from the dock
+        #[cfg(target_os = "macos")]
+        if cfg.skip_taskbar {
+            use tao::platform::macos::{ActivationPolicy, EventLoopWindowTargetExtMacOS};
+            shared
+                .target
+                .set_activation_policy_at_runtime(ActivationPolicy::Accessory);
+        }
+
+        if let Some(effect) = cfg.background_effect {
+            apply_background_effect(&window, effect);
+        }
+        if let Some(radius) = cfg.corner_radius {
+            apply_corner_radius(&window, radius);
+        }
+
         // https://developer.apple.com/documentation/appkit/nswindowcollectionbehavior/nswindowcollectionbehaviormanaged
         #[cfg(target_os = "macos")]
         {
@@ -220,10 +330,17 @@ impl WebviewInstance {
             }
         }
 
-        let mut web_context = WebContext::new(cfg.data_dir.clone());
+        let data_dir = cfg.data_dir.clone().filter(|dir| check_data_dir(dir));
+        let mut web_context = WebContext::new(data_dir);
         let edit_queue = WryQueue::default();
         let asset_handlers = AssetHandlerRegistry::new();
-        let edits = WebviewEdits::new(dom.runtime(), edit_queue.clone());
+        let invalid_messages = Rc::new(InvalidMessages::new(cfg.strict_ipc));
+        let edits = WebviewEdits::new(
+            dom.runtime(),
+            edit_queue.clone(),
+            cfg.render_observer.clone(),
+            invalid_messages.clone(),
+        );
         let file_hover = NativeFileHover::default();
         let headless = !cfg.window.window.visible;
 
@@ -231,6 +348,7 @@ impl WebviewInstance {
             to_owned![
                 cfg.custom_head,
                 cfg.custom_index,
+                cfg.csp,
                 cfg.root_name,
                 asset_handlers,
                 edits
@@ -243,6 +361,7 @@ impl WebviewInstance {
                     &edits,
                     custom_head.clone(),
                     custom_index.clone(),
+                    csp.as_deref(),
                     &root_name,
                     headless,
                 )
@@ -251,12 +370,21 @@ impl WebviewInstance {
 
         let ipc_handler = {
             let window_id = window.id();
-            to_owned![shared.proxy];
+            to_owned![shared.proxy, invalid_messages];
             move |payload: wry::http::Request<String>| {
+                let from_app = is_app_url(&payload.uri().to_string());
+
                 // defer the event to the main thread
                 let body = payload.into_body();
-                if let Ok(msg) = serde_json::from_str(&body) {
-                    _ = proxy.send_event(UserWindowEvent::Ipc { id: window_id, msg });
+                match serde_json::from_str::<IpcMessage>(&body) {
+                    Ok(msg) if !from_app && !msg.allowed_from_any_page() => {
+                        tracing::warn!(
+                            "Dropped a {:?} message from a page that isn't the app's",
+                            msg.method()
+                        )
+                    }
+                    Ok(msg) => _ = proxy.send_event(UserWindowEvent::Ipc { id: window_id, msg }),
+                    Err(err) => invalid_messages.drop_message("ipc message", err, &body),
                 }
             }
         };
@@ -264,10 +392,15 @@ impl WebviewInstance {
         let file_drop_handler = {
             to_owned![file_hover];
 
-            #[cfg(windows)]
             let (proxy, window_id) = (shared.proxy.to_owned(), window.id());
 
             move |evt: DragDropEvent| {
+                // Let any components listening for native file drops know about the event
+                _ = proxy.send_event(UserWindowEvent::FileDrop {
+                    id: window_id,
+                    event: evt.clone(),
+                });
+
                 // Update the most recent file drop event - when the event comes in from the webview we can use the
                 // most recent event to build a new event with the files in it.
                 #[cfg(not(windows))]
@@ -335,6 +468,12 @@ impl WebviewInstance {
             webview = webview.with_browser_accelerator_keys(false);
         }
 
+        let external_link_policy = cfg.external_link_policy;
+        let navigation_policy = cfg.navigation_policy.clone();
+        let new_window_policy = cfg.navigation_policy.clone();
+        let opened_for_navigation = cfg.opened_for_navigation;
+        let (proxy, window_id) = (shared.proxy.to_owned(), window.id());
+        let navigation_proxy = proxy.clone();
         webview = webview
             .with_bounds(wry::Rect {
                 position: wry::dpi::Position::Logical(wry::dpi::LogicalPosition::new(0.0, 0.0)),
@@ -346,26 +485,117 @@ impl WebviewInstance {
             .with_transparent(cfg.window.window.transparent)
             .with_url("dioxus://index.html/")
             .with_ipc_handler(ipc_handler)
-            .with_navigation_handler(|var| {
-                // We don't want to allow any navigation
-                // We only want to serve the index file and assets
-                if var.starts_with("dioxus://") || var.starts_with("http://dioxus.") {
-                    true
-                } else {
-                    if var.starts_with("http://") || var.starts_with("https://") {
-                        _ = webbrowser::open(&var);
+            .with_navigation_handler(move |var| {
+                // The index file and assets are always allowed, everything else is up to the navigation policy
+                if is_app_url(&var) {
+                    return true;
+                }
+
+                match decide_navigation(navigation_policy.as_ref(), &var) {
+                    NavigationDecision::Allow => true,
+                    NavigationDecision::Deny => false,
+                    NavigationDecision::OpenExternally => {
+                        external_link_policy.open(&var);
+                        false
                     }
-                    false
+                    NavigationDecision::OpenInNewWindow if opened_for_navigation => true,
+                    NavigationDecision::OpenInNewWindow => {
+                        _ = navigation_proxy.send_event(UserWindowEvent::OpenInNewWindow {
+                            opener: window_id,
+                            url: var,
+                        });
+                        false
+                    }
+                }
+            })
+            .with_new_window_req_handler(move |var| {
+                // window.open and target=_blank links would otherwise open a bare webview window. The app's own pages
+                // only resolve inside of the webview, so they are never handed to the system browser.
+                if is_app_url(&var) {
+                    tracing::warn!("Denied opening the app's own page {var} in a new window");
+                    return false;
                 }
-            }) // prevent all navigations
+
+                match decide_navigation(new_window_policy.as_ref(), &var) {
+                    NavigationDecision::OpenInNewWindow if !opened_for_navigation => {
+                        _ = proxy.send_event(UserWindowEvent::OpenInNewWindow {
+                            opener: window_id,
+                            url: var,
+                        });
+                    }
+                    NavigationDecision::Allow | NavigationDecision::OpenInNewWindow => {
+                        _ = proxy.send_event(UserWindowEvent::LoadUrl {
+                            id: window_id,
+                            url: var,
+                        });
+                    }
+                    NavigationDecision::Deny => {}
+                    NavigationDecision::OpenExternally => external_link_policy.open(&var),
+                }
+                false
+            })
             .with_asynchronous_custom_protocol(String::from("dioxus"), request_handler)
-            .with_web_context(&mut web_context)
-            .with_drag_drop_handler(file_drop_handler);
+            .with_web_context(&mut web_context);
+
+        if !cfg.disable_drag_drop_handler {
+            webview = webview.with_drag_drop_handler(file_drop_handler);
+        }
+
+        if cfg.zoom_hotkeys {
+            webview = webview.with_hotkeys_zoom(true);
+        }
 
         if let Some(color) = cfg.background_color {
             webview = webview.with_background_color(color);
         }
 
+        if let Some(mut handler) = cfg.download_handler.take() {
+            let (proxy, window_id) = (shared.proxy.to_owned(), window.id());
+
+            webview = webview
+                .with_download_started_handler(move |url, destination| {
+                    let request = DownloadRequest::new(url, destination);
+                    match handler(request) {
+                        Some(path) if download::check_destination(&path) => {
+                            *destination = path;
+                            true
+                        }
+                        _ => false,
+                    }
+                })
+                .with_download_completed_handler(move |url, path, success| {
+                    _ = proxy.send_event(UserWindowEvent::DownloadCompleted {
+                        id: window_id,
+                        download: DownloadCompleted { url, path, success },
+                    });
+                });
+        }
+
+        if let Some(user_agent) = &cfg.user_agent {
+            webview = webview.with_user_agent(user_agent);
+        }
+
+        if let Some(proxy) = &cfg.proxy {
+            webview = webview.with_proxy_config(proxy.to_wry());
+        }
+
+        webview = webview.with_autoplay(cfg.autoplay_policy == AutoplayPolicy::AllowAll);
+
+        #[cfg(target_os = "windows")]
+        if let Some(args) = webview2_browser_args(&cfg) {
+            use wry::WebViewBuilderExtWindows;
+            webview = webview.with_additional_browser_args(args);
+        }
+
+        if cfg.incognito {
+            webview = webview.with_incognito(true);
+        }
+
+        let headers = cfg.header_map().unwrap_or_default();
+        if !headers.is_empty() {
+            webview = webview.with_headers(headers.clone());
+        }
+
         for (name, handler) in cfg.protocols.drain(..) {
             webview = webview.with_custom_protocol(name, handler);
         }
@@ -374,6 +604,29 @@ impl WebviewInstance {
             webview = webview.with_asynchronous_custom_protocol(name, handler);
         }
 
+        for script in &cfg.init_scripts {
+            webview = webview.with_initialization_script(script);
+        }
+
+        // Elements with a data-drag-region attribute act like a titlebar
+        const DRAG_REGION_SCRIPT: &str = r#"
+        document.addEventListener('mousedown', function(e) {
+            if (e.button !== 0 || !(e.target instanceof Element)) return;
+            let region = e.target.closest('[data-drag-region]');
+            if (region == null) return;
+            let control = e.target.closest('button, a, input, select, textarea, label, [contenteditable], [data-no-drag]');
+            if (control != null && region.contains(control)) return;
+            // Keep the text under the cursor from being selected while the window moves
+            e.preventDefault();
+            let method = e.detail === 2 ? 'toggle_maximize' : 'drag_window';
+            window.ipc.postMessage(JSON.stringify({ method: method, params: null }));
+        });
+        "#;
+        webview = webview.with_initialization_script(DRAG_REGION_SCRIPT);
+        webview = webview.with_initialization_script(INVOKE_SCRIPT);
+        webview = webview.with_initialization_script(MEDIA_PREFERENCES_SCRIPT);
+        webview = webview.with_initialization_script(ONLINE_STATUS_SCRIPT);
+
         const INITIALIZATION_SCRIPT: &str = r#"
         if (document.addEventListener) {
             document.addEventListener('contextmenu', function(e) {
@@ -394,17 +647,37 @@ impl WebviewInstance {
             webview = webview.with_devtools(true);
         }
 
-        let webview = webview.build().unwrap();
+        if cfg.context_menu.is_some() {
+            webview = webview.with_initialization_script(CUSTOM_MENU_SCRIPT);
+        }
+
+        let webview = webview
+            .build()
+            .unwrap_or_else(|err| panic!("{}", LaunchError::webview_unavailable(err)));
+
+        if cfg.ignore_certificate_errors {
+            crate::certificates::ignore_certificate_errors(&webview);
+        }
+
+        if let Some(handler) = cfg.error_page.take() {
+            crate::error_page::install_error_page(&webview, handler);
+        }
+
+        #[cfg(target_os = "linux")]
+        if let Some(accept_language) = &cfg.accept_language {
+            crate::locale::apply_accept_language(&webview, accept_language);
+        }
 
-        let menu = if cfg!(not(any(target_os = "android", target_os = "ios"))) {
-            let menu_option = cfg.menu.into();
-            if let Some(menu) = &menu_option {
+        #[cfg(not(any(target_os = "android", target_os = "ios")))]
+        let menu = {
+            let menu = crate::accelerators::build_menu_bar(cfg.menu, &cfg.accelerators);
+            if let Some(menu) = &menu {
                 crate::menubar::init_menu_bar(menu, &window);
             }
-            menu_option
-        } else {
-            None
+            menu
         };
+        #[cfg(any(target_os = "android", target_os = "ios"))]
+        let menu = cfg.menu.into();
 
         let desktop_context = Rc::from(DesktopService::new(
             webview,
@@ -412,28 +685,107 @@ impl WebviewInstance {
             shared.clone(),
             asset_handlers,
             file_hover,
+            invalid_messages,
+            cfg.user_agent.clone(),
         ));
 
+        desktop_context
+            .size_constraints
+            .set(cfg.window.window.inner_size_constraints);
+
+        // The page isn't loaded yet, so the attribute is set again once the interpreter starts
+        if let Some(enabled) = cfg.spellcheck {
+            desktop_context.set_spellcheck(enabled);
+        }
+
+        if cfg.centered {
+            desktop_context.center_on_active_monitor();
+        }
+
+        if cfg.open_devtools && cfg!(debug_assertions) {
+            desktop_context.open_devtools();
+        }
+
         // Provide the desktop context to the virtual dom and edit handler
         edits.set_desktop_context(Rc::downgrade(&desktop_context));
-        let provider: Rc<dyn Document> = Rc::new(DesktopDocument::new(desktop_context.clone()));
-        let history_provider: Rc<dyn History> = Rc::new(MemoryHistory::default());
-        dom.in_runtime(|| {
-            ScopeId::ROOT.provide_context(desktop_context.clone());
-            ScopeId::ROOT.provide_context(provider);
-            ScopeId::ROOT.provide_context(history_provider);
-        });
+        provide_desktop_contexts(&dom, &desktop_context);
 
         WebviewInstance {
             dom,
             edits,
             waker: tao_waker(shared.proxy.clone(), desktop_context.window.id()),
             desktop_context,
+            exit_app_on_close: cfg.exit_app_on_close,
+            external_link_policy,
+            opened_window_settings,
+            initialized: false,
+            pending_navigation: None,
+            headers,
             _menu: menu,
             _web_context: web_context,
+            #[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
+            context_menu: cfg
+                .context_menu
+                .as_deref()
+                .map(crate::context_menu::build_menu),
         }
     }
 
+    /// Replace the root component of this window with a new virtual dom, keeping the window itself.
+    ///
+    /// The old virtual dom is dropped and the page is reloaded, which makes the interpreter ask us to rebuild.
+    pub fn replace_root(&mut self, dom: VirtualDom) {
+        provide_desktop_contexts(&dom, &self.desktop_context);
+        self.edits.set_runtime(dom.runtime());
+        self.edits.wry_queue.reset();
+
+        // The handler belongs to a scope of the old app
+        self.desktop_context.remove_close_handler();
+        self.dom = dom;
+
+        if let Err(err) = self
+            .desktop_context
+            .webview
+            .evaluate_script("window.location.reload()")
+        {
+            tracing::error!(
+                "Failed to reload the webview after replacing the root component: {err}"
+            );
+        }
+    }
+
+    /// Point the webview somewhere else.
+    ///
+    /// Navigating before the page has loaded would race the interpreter bootstrapping, so the navigation is queued until
+    /// the page asks for its first render.
+    pub fn navigate(&mut self, navigation: Navigation) {
+        if !self.initialized {
+            self.pending_navigation = Some(navigation);
+            return;
+        }
+
+        // Whatever the webview was showing is gone, including any edits it never asked for. If the new page loads the
+        // interpreter, it will ask for a full rebuild just like it does at launch.
+        self.edits.wry_queue.reset();
+
+        let webview = &self.desktop_context.webview;
+        let result = match &navigation {
+            Navigation::Reload => webview.evaluate_script("window.location.reload()"),
+            Navigation::Url(url) if self.headers.is_empty() => webview.load_url(url),
+            Navigation::Url(url) => webview.load_url_with_headers(url, self.headers.clone()),
+            Navigation::Html(html) => webview.load_html(html),
+        };
+
+        if let Err(err) = result {
+            tracing::error!("Failed to navigate the webview to {navigation:?}: {err}");
+        }
+    }
+
+    /// Take the navigation that was requested before the page finished loading
+    pub fn take_pending_navigation(&mut self) -> Option<Navigation> {
+        self.pending_navigation.take()
+    }
+
     pub fn poll_vdom(&mut self) {
         let mut cx = std::task::Context::from_waker(&self.waker);
 
@@ -462,10 +814,33 @@ impl WebviewInstance {
             // lock the hack-ed in lock sync wry has some thread-safety issues with event handlers
             let _lock = crate::android_sync_lock::android_runtime_lock();
 
-            self.edits
-                .wry_queue
-                .with_mutation_state_mut(|f| self.dom.render_immediate(f));
-            self.edits.wry_queue.send_edits();
+            // Counting the mutations is cheap, timing the render is only worth it if someone is looking
+            let observer = &self.edits.render_observer;
+            let timed = observer.is_some() || tracing::enabled!(tracing::Level::TRACE);
+            if let Some(observer) = observer {
+                observer.on_render_start();
+            }
+            let started = timed.then(std::time::Instant::now);
+            let mutation_count = self.edits.wry_queue.with_mutation_state_mut(|f| {
+                let mut counter = CountingMutations::new(f);
+                self.dom.render_immediate(&mut counter);
+                counter.count
+            });
+            if let Some(elapsed) = started.map(|started| started.elapsed()) {
+                tracing::trace!(
+                    "Rendered window {:?} with {mutation_count} mutations in {elapsed:?}",
+                    self.desktop_context.window.id()
+                );
+                if let Some(observer) = observer {
+                    observer.on_render_complete(elapsed, mutation_count);
+                }
+            }
+
+            // A render that changed nothing leaves nothing for the webview to apply. Templates are only written as
+            // part of the mutation that first loads them, so a batch with new templates is never empty.
+            if mutation_count > 0 {
+                self.edits.wry_queue.send_edits();
+            }
         }
     }
 
@@ -480,6 +855,17 @@ impl WebviewInstance {
     }
 }
 
+/// Where to point a webview with [`WebviewInstance::navigate`]
+#[derive(Debug, Clone)]
+pub(crate) enum Navigation {
+    /// Reload the current page
+    Reload,
+    /// Load a url
+    Url(String),
+    /// Load a string of html
+    Html(String),
+}
+
 /// A synchronous response to a browser event which may prevent the default browser's action
 #[derive(serde::Serialize, Default)]
 pub struct SynchronousEventResponse {
@@ -494,3 +880,177 @@ impl SynchronousEventResponse {
         Self { prevent_default }
     }
 }
+
+/// If the url points at the index file or the assets served by the app
+fn is_app_url(url: &str) -> bool {
+    url.starts_with("dioxus://") || url.starts_with("http://dioxus.")
+}
+
+/// Decide what happens to a navigation away from the app's own page
+fn decide_navigation(policy: Option<&NavigationPolicy>, url: &str) -> NavigationDecision {
+    let Some(policy) = policy else {
+        return NavigationDecision::OpenExternally;
+    };
+
+    match url::Url::parse(url) {
+        Ok(url) => policy(&url),
+        Err(err) => {
+            tracing::warn!("Denied a navigation to {url:?}, which isn't a valid url: {err}");
+            NavigationDecision::Deny
+        }
+    }
+}
+
+/// Draw a native effect behind a transparent window
+fn apply_background_effect(window: &tao::window::Window, effect: crate::BlurMode) {
+    #[cfg(target_os = "windows")]
+    let result = match effect {
+        crate::BlurMode::Blur => window_vibrancy::apply_blur(window, None),
+        crate::BlurMode::Acrylic => window_vibrancy::apply_acrylic(window, None),
+        crate::BlurMode::Mica => window_vibrancy::apply_mica(window, None),
+    };
+
+    #[cfg(target_os = "macos")]
+    let result = {
+        use window_vibrancy::NSVisualEffectMaterial;
+        let material = match effect {
+            crate::BlurMode::Blur => NSVisualEffectMaterial::WindowBackground,
+            crate::BlurMode::Acrylic => NSVisualEffectMaterial::HudWindow,
+            crate::BlurMode::Mica => NSVisualEffectMaterial::UnderWindowBackground,
+        };
+        window_vibrancy::apply_vibrancy(window, material, None, None)
+    };
+
+    #[cfg(any(target_os = "windows", target_os = "macos"))]
+    if let Err(err) = result {
+        tracing::error!("Failed to apply the {effect:?} background effect: {err}");
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    {
+        _ = window;
+        tracing::warn!("The {effect:?} background effect is not supported on this platform, the window is only transparent");
+    }
+}
+
+/// Round the corners of the window, as far as the platform lets apps pick them
+fn apply_corner_radius(window: &tao::window::Window, radius: f32) {
+    #[cfg(target_os = "windows")]
+    {
+        use tao::platform::windows::WindowExtWindows;
+        use windows::Win32::{
+            Foundation::HWND,
+            Graphics::Dwm::{
+                DwmSetWindowAttribute, DWMWA_WINDOW_CORNER_PREFERENCE, DWMWCP_DONOTROUND,
+                DWMWCP_ROUND, DWMWCP_ROUNDSMALL,
+            },
+        };
+
+        // The small corners have a radius of 4 pixels and the regular ones 8
+        let preference = if radius <= 0.0 {
+            DWMWCP_DONOTROUND
+        } else if radius < 6.0 {
+            DWMWCP_ROUNDSMALL
+        } else {
+            DWMWCP_ROUND
+        };
+        let result = unsafe {
+            DwmSetWindowAttribute(
+                HWND(window.hwnd() as _),
+                DWMWA_WINDOW_CORNER_PREFERENCE,
+                &preference as *const _ as *const _,
+                std::mem::size_of_val(&preference) as u32,
+            )
+        };
+        if let Err(err) = result {
+            tracing::warn!(
+                "Failed to round the corners of the window, which needs Windows 11: {err}"
+            );
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        use cocoa::base::{id, YES};
+        use objc::{msg_send, sel, sel_impl};
+        use tao::platform::macos::WindowExtMacOS;
+
+        #[allow(unexpected_cfgs)]
+        unsafe {
+            let view: id = window.ns_view() as id;
+            let _: () = msg_send![view, setWantsLayer: YES];
+            let layer: id = msg_send![view, layer];
+            let _: () = msg_send![layer, setCornerRadius: radius as f64];
+            let _: () = msg_send![layer, setMasksToBounds: YES];
+
+            // The shadow is drawn from the shape of the window when it was last computed
+            let window: id = window.ns_window() as id;
+            let _: () = msg_send![window, invalidateShadow];
+        }
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    {
+        _ = window;
+        tracing::warn!("A corner radius of {radius} can't be set on this platform, round the root element with CSS instead");
+    }
+}
+
+/// The arguments WebView2 starts with, if the config needs more than wry passes by default.
+///
+/// Passing any arguments replaces the ones wry would pass, so its defaults and the proxy are repeated here.
+#[cfg(target_os = "windows")]
+fn webview2_browser_args(cfg: &Config) -> Option<String> {
+    let autoplay = match cfg.autoplay_policy {
+        AutoplayPolicy::AllowAll => return None,
+        AutoplayPolicy::RequireUserGesture => "user-gesture-required",
+        // Chromium lets muted media play, and media with sound once the user interacted with the page
+        AutoplayPolicy::AllowMutedOnly => "document-user-activation-required",
+    };
+
+    let mut args = String::from("--disable-features=msWebOOUI,msPdfOOUI,msSmartScreenProtection");
+    args.push_str(&format!(" --autoplay-policy={autoplay}"));
+    if let Some(proxy) = &cfg.proxy {
+        let scheme = match proxy.kind {
+            crate::config::ProxyKind::Http => "http",
+            crate::config::ProxyKind::Socks5 => "socks5",
+        };
+        args.push_str(&format!(
+            " --proxy-server={scheme}://{}:{}",
+            proxy.host, proxy.port
+        ));
+    }
+
+    Some(args)
+}
+
+/// Make sure the webview will be able to keep its profile in the data directory.
+///
+/// The webviews don't report this themselves, they just silently lose data or fail to start.
+fn check_data_dir(dir: &std::path::Path) -> bool {
+    let probe = dir.join(".dioxus-write-check");
+    let result = std::fs::create_dir_all(dir)
+        .and_then(|_| std::fs::write(&probe, []))
+        .and_then(|_| std::fs::remove_file(&probe));
+
+    if let Err(err) = result {
+        tracing::error!(
+            "The data directory {} is not writable, cookies and local storage will not be persisted: {err}",
+            dir.display()
+        );
+        return false;
+    }
+
+    true
+}
+
+/// Provide the contexts every desktop app expects to the root of a virtual dom
+fn provide_desktop_contexts(dom: &VirtualDom, desktop_context: &DesktopContext) {
+    let provider: Rc<dyn Document> = Rc::new(DesktopDocument::new(desktop_context.clone()));
+    let history_provider: Rc<dyn History> = Rc::new(MemoryHistory::default());
+    dom.in_runtime(|| {
+        ScopeId::ROOT.provide_context(desktop_context.clone());
+        ScopeId::ROOT.provide_context(provider);
+        ScopeId::ROOT.provide_context(history_provider);
+    });
+}
@@ -194,22 +194,22 @@ impl TouchPoint {
     }
 
     /// A unique identifier for this touch point that will be the same for the duration of the touch
-    fn identifier(&self) -> i32 {
+    pub fn identifier(&self) -> i32 {
         self.inner.identifier()
     }
 
     /// the pressure of the touch
-    fn force(&self) -> f64 {
+    pub fn force(&self) -> f64 {
         self.inner.force()
     }
 
     /// the radius of the touch
-    fn radius(&self) -> ScreenPoint {
+    pub fn radius(&self) -> ScreenPoint {
         self.inner.radius()
     }
 
     /// the rotation of the touch in degrees between 0 and 90
-    fn rotation(&self) -> f64 {
+    pub fn rotation(&self) -> f64 {
         self.inner.rotation()
     }
 
@@ -279,6 +279,50 @@ fn test_back_and_forth() {
     assert_eq!(data, p);
 }
 
+#[test]
+fn test_multi_touch() {
+    use crate::prelude::InteractionLocation;
+
+    let o = r#"
+{
+  "element": 0,
+  "name": "touchmove",
+  "bubbles": true,
+  "data": {
+    "alt_key": false,
+    "ctrl_key": false,
+    "meta_key": false,
+    "shift_key": false,
+    "touches": [
+      { "identifier": 0, "client_x": 10, "client_y": 20, "page_x": 10, "page_y": 20, "screen_x": 110, "screen_y": 120, "force": 1, "radius_x": 5, "radius_y": 5, "rotation_angle": 0 },
+      { "identifier": 1, "client_x": 50, "client_y": 60, "page_x": 50, "page_y": 60, "screen_x": 150, "screen_y": 160, "force": 1, "radius_x": 5, "radius_y": 5, "rotation_angle": 0 }
+    ],
+    "changed_touches": [
+      { "identifier": 1, "client_x": 50, "client_y": 60, "page_x": 50, "page_y": 60, "screen_x": 150, "screen_y": 160, "force": 1, "radius_x": 5, "radius_y": 5, "rotation_angle": 0 }
+    ],
+    "target_touches": []
+  }
+}
+    "#;
+
+    let p: HtmlEvent = serde_json::from_str(o).unwrap();
+    let EventData::Touch(data) = p.data else {
+        panic!("expected a touch event, got {:?}", p.data);
+    };
+
+    let data = crate::TouchData::from(data);
+    let touches = data.touches();
+    assert_eq!(touches.len(), 2);
+    assert_eq!(touches[0].identifier(), 0);
+    assert_eq!(touches[1].identifier(), 1);
+    assert_eq!(touches[1].client_coordinates().x, 50.0);
+    assert_eq!(touches[1].client_coordinates().y, 60.0);
+
+    let changed = data.touches_changed();
+    assert_eq!(changed.len(), 1);
+    assert_eq!(changed[0].identifier(), 1);
+}
+
 /// A trait for converting from a serialized event to a concrete event type.
 pub struct SerializedHtmlEventConverter;
 
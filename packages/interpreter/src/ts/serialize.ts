@@ -218,12 +218,29 @@ function serializeTouchEvent(event: TouchEvent): SerializedEvent {
     ctrl_key: event.ctrlKey,
     meta_key: event.metaKey,
     shift_key: event.shiftKey,
-    changed_touches: event.changedTouches,
-    target_touches: event.targetTouches,
-    touches: event.touches,
+    changed_touches: serializeTouchList(event.changedTouches),
+    target_touches: serializeTouchList(event.targetTouches),
+    touches: serializeTouchList(event.touches),
   };
 }
 
+// Touch lists and touches only have getters, so JSON.stringify would turn them into empty objects
+function serializeTouchList(list: TouchList): SerializedEvent[] {
+  return Array.from(list, (touch) => ({
+    identifier: touch.identifier,
+    client_x: touch.clientX,
+    client_y: touch.clientY,
+    page_x: touch.pageX,
+    page_y: touch.pageY,
+    screen_x: touch.screenX,
+    screen_y: touch.screenY,
+    force: touch.force,
+    radius_x: touch.radiusX,
+    radius_y: touch.radiusY,
+    rotation_angle: touch.rotationAngle,
+  }));
+}
+
 function serializePointerEvent(event: PointerEvent): SerializedEvent {
   return {
     alt_key: event.altKey,